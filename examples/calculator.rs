@@ -1,19 +1,14 @@
-use hanbun;
-
 use crossterm::{
     event::{read, Event, KeyCode},
     terminal::{disable_raw_mode, enable_raw_mode},
 };
 
-use meval;
-
 use std::process;
 
 #[derive(Debug)]
 struct Button {
     x: usize,
     y: usize,
-    digit: usize,
 }
 
 // Generates a grid of cells (not to be confused with the terminal's cells).
@@ -61,7 +56,6 @@ fn grid(
             buttons.push(Button {
                 x: x_center,
                 y: y_center,
-                digit: 0,
             });
         }
     }
@@ -86,7 +80,7 @@ fn main() -> std::result::Result<(), ()> {
             );
             process::exit(1);
         }
-        buffer = hanbun::Buffer::new(width as usize, height as usize, ' ');
+        buffer = hanbun::Buffer::new(width, height, ' ');
     } else {
         eprintln!("Unable to get terminal size");
         process::exit(1);
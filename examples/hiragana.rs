@@ -30,7 +30,7 @@ fn main() {
 
         for (y, row) in glyph.iter().enumerate() {
             for bit in 0..8 {
-                match *row & 1 << bit as usize {
+                match *row & 1 << bit {
                     0 => {}
                     _ => {
                         let x = alignment + bit;
@@ -35,8 +35,11 @@ fn main() {
                         );
                     }
                 }
-                buffer.draw();
-                buffer.clear(' ');
+                // The output might be piped somewhere that closes early
+                if let Err(error) = buffer.try_draw() {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
 
                 return;
             } else {
@@ -21,18 +21,13 @@ fn main() {
     // Here we store the state of each cell
     let mut buffer = hanbun::Buffer::new(width, height, ' ');
 
-    let mut x = 0;
-    let mut y = 0;
-    for line in &lines {
-        for char in line.chars() {
+    for (y, line) in lines.iter().enumerate() {
+        for (x, char) in line.chars().enumerate() {
             // We set a colored half block for each W we find
             if char == 'W' {
                 buffer.color(x, y, Color::Green);
             }
-            x += 1;
         }
-        y += 1;
-        x = 0;
     }
 
     // Add some centered text to the bottom
@@ -58,21 +58,16 @@ fn w_to_half_block(
     string: &str,
     function: fn(usize, usize, &mut hanbun::Buffer),
 ) {
-    let mut x = 0;
-    let mut y = 0;
-    for line in string.lines() {
-        for char in line.chars() {
+    for (y, line) in string.lines().enumerate() {
+        for (x, char) in line.chars().enumerate() {
             if char == 'W' {
                 function(cursor.x + x, cursor.y + y, buffer);
             }
-            x += 1;
         }
-        y += 1;
-        x = 0;
     }
 }
 
-fn clear_space(buffer: &mut hanbun::Buffer, cleared_spaces: &mut Vec<Cursor>, cursor: &Cursor) {
+fn clear_space(buffer: &mut hanbun::Buffer, cleared_spaces: &mut [Cursor], cursor: &Cursor) {
     for y in 0..Cursor::CHARACTER_SIZE {
         // The space's brightness depends on how often the space has been cleared
         let count = cleared_spaces
@@ -83,7 +78,7 @@ fn clear_space(buffer: &mut hanbun::Buffer, cleared_spaces: &mut Vec<Cursor>, cu
             0 => "     ",
             1 => "░░░░░",
             2 => "▒▒▒▒▒",
-            3 | _ => "▓▓▓▓▓",
+            _ => "▓▓▓▓▓",
         };
         buffer.colored_print(cursor.x, cursor.y + y, clear_character, Color::DarkGrey);
     }
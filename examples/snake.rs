@@ -107,7 +107,7 @@ fn main() -> result::Result<(), String> {
 
 fn r#move(
     buffer: &mut hanbun::Buffer,
-    parts: &mut Vec<Position>,
+    parts: &mut [Position],
     direction: &Direction,
     score: usize,
 ) {
@@ -214,25 +214,17 @@ fn handle_input(direction: &mut Direction, delay: u64) {
         // Next `read` is guaranteed not to block if `poll` returns `Ok(true)`
         if let Event::Key(event) = read().unwrap() {
             match event.code {
-                KeyCode::Char('w') => {
-                    if *direction != Direction::Down {
-                        *direction = Direction::Up;
-                    }
+                KeyCode::Char('w') if *direction != Direction::Down => {
+                    *direction = Direction::Up;
                 }
-                KeyCode::Char('s') => {
-                    if *direction != Direction::Up {
-                        *direction = Direction::Down;
-                    }
+                KeyCode::Char('s') if *direction != Direction::Up => {
+                    *direction = Direction::Down;
                 }
-                KeyCode::Char('a') => {
-                    if *direction != Direction::Right {
-                        *direction = Direction::Left;
-                    }
+                KeyCode::Char('a') if *direction != Direction::Right => {
+                    *direction = Direction::Left;
                 }
-                KeyCode::Char('d') => {
-                    if *direction != Direction::Left {
-                        *direction = Direction::Right;
-                    }
+                KeyCode::Char('d') if *direction != Direction::Left => {
+                    *direction = Direction::Right;
                 }
                 KeyCode::Esc => {
                     disable_raw_mode().unwrap();
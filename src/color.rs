@@ -0,0 +1,199 @@
+use crate::Color;
+use std::env;
+
+/// How many colors the terminal can display.
+///
+/// Colors that the terminal can't display are mapped to the closest color it can display when drawing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// No colors at all. No escape sequences are written.
+    /// How colors are displayed instead is decided by [`MonochromeStyle`].
+    ///
+    /// Because moving the cursor needs escape sequences too, every draw that changes something
+    /// draws the whole buffer again on the next rows.
+    /// Only [`Origin::Position`](crate::Origin::Position) still moves the cursor.
+    Monochrome,
+    /// Only the 16 named colors like [`Color::Red`] and [`Color::DarkGrey`].
+    Ansi16,
+    /// The 256 colors of [`Color::AnsiValue`].
+    Ansi256,
+    /// All 24-bit [`Color::Rgb`] colors.
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Detects the terminal's color depth using the `NO_COLOR`, `COLORTERM` and `TERM` environment variables.
+    ///
+    /// If `NO_COLOR` is set to anything, [`ColorDepth::Monochrome`] is returned
+    /// (see <https://no-color.org>).
+    /// If nothing is known, [`ColorDepth::Ansi16`] is assumed.
+    pub fn detect() -> ColorDepth {
+        if env::var_os("NO_COLOR").is_some_and(|no_color| !no_color.is_empty()) {
+            return ColorDepth::Monochrome;
+        }
+
+        if let Ok(colorterm) = env::var("COLORTERM") {
+            if colorterm == "truecolor" || colorterm == "24bit" {
+                return ColorDepth::TrueColor;
+            }
+        }
+
+        match env::var("TERM") {
+            Ok(term) if term == "dumb" => ColorDepth::Monochrome,
+            Ok(term) if term.ends_with("-direct") => ColorDepth::TrueColor,
+            Ok(term) if term.contains("256color") => ColorDepth::Ansi256,
+            _ => ColorDepth::Ansi16,
+        }
+    }
+}
+
+/// How the closest color is chosen when a color has to be mapped to one the terminal can display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantization {
+    /// Chooses the color with the smallest distance in RGB space.
+    Nearest,
+    /// Like [`Quantization::Nearest`] but weighs the red, green and blue components
+    /// by how sensitive the human eye is to them, which usually looks better.
+    Perceptual,
+}
+
+/// How colored cells are displayed with [`ColorDepth::Monochrome`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonochromeStyle {
+    /// Half blocks are drawn as plain `▀`, `▄` and `█` regardless of their colors.
+    Blocks,
+    /// Cells with half blocks are drawn as one of ` `, `░`, `▒`, `▓` and `█`
+    /// depending on how bright their colors are.
+    /// This trades the vertical resolution for brightness.
+    Shades,
+}
+
+/// The RGB values of the 16 named colors as most terminals display them, in ANSI order.
+const NAMED_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (205, 0, 0)),
+    (Color::DarkGreen, (0, 205, 0)),
+    (Color::DarkYellow, (205, 205, 0)),
+    (Color::DarkBlue, (0, 0, 238)),
+    (Color::DarkMagenta, (205, 0, 205)),
+    (Color::DarkCyan, (0, 205, 205)),
+    (Color::Grey, (229, 229, 229)),
+    (Color::DarkGrey, (127, 127, 127)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (92, 92, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// The intensities of the 6x6x6 color cube of the 256 colors.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Returns the RGB values of `color` or [`None`] for [`Color::Reset`].
+pub(crate) fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb { r, g, b } => Some((r, g, b)),
+        Color::AnsiValue(value) => Some(ansi_value_to_rgb(value)),
+        color => NAMED_COLORS
+            .iter()
+            .find(|(named_color, _)| *named_color == color)
+            .map(|(_, rgb)| *rgb),
+    }
+}
+
+fn ansi_value_to_rgb(value: u8) -> (u8, u8, u8) {
+    match value {
+        0..=15 => NAMED_COLORS[value as usize].1,
+        16..=231 => {
+            let index = value - 16;
+            (
+                CUBE_LEVELS[(index / 36) as usize],
+                CUBE_LEVELS[(index / 6 % 6) as usize],
+                CUBE_LEVELS[(index % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + (value - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+/// Returns the relative luminance of `color` from 0.0 to 1.0.
+///
+/// [`Color::Reset`] is assumed to be bright.
+pub(crate) fn luminance(color: Color) -> f32 {
+    if let Some((red, green, blue)) = to_rgb(color) {
+        (0.2126 * red as f32 + 0.7152 * green as f32 + 0.0722 * blue as f32) / 255.0
+    } else {
+        1.0
+    }
+}
+
+/// Returns the average of `a` and `b`.
+pub(crate) fn blend(a: Color, b: Color) -> Color {
+    if a == b {
+        return a;
+    }
+
+    match (to_rgb(a), to_rgb(b)) {
+        (Some(a), Some(b)) => Color::Rgb {
+            r: ((a.0 as u16 + b.0 as u16) / 2) as u8,
+            g: ((a.1 as u16 + b.1 as u16) / 2) as u8,
+            b: ((a.2 as u16 + b.2 as u16) / 2) as u8,
+        },
+        (Some(_), None) => a,
+        _ => b,
+    }
+}
+
+/// Returns how different `a` and `b` look.
+pub(crate) fn distance(a: (u8, u8, u8), b: (u8, u8, u8), quantization: Quantization) -> u32 {
+    let red = (a.0 as i32 - b.0 as i32).pow(2) as u32;
+    let green = (a.1 as i32 - b.1 as i32).pow(2) as u32;
+    let blue = (a.2 as i32 - b.2 as i32).pow(2) as u32;
+    match quantization {
+        Quantization::Nearest => red + green + blue,
+        Quantization::Perceptual => {
+            // The "redmean" approximation, scaled by 256 to stay in integers
+            let red_mean = (a.0 as u32 + b.0 as u32) / 2;
+            (512 + red_mean) * red + 1024 * green + (767 - red_mean) * blue
+        }
+    }
+}
+
+/// Maps `color` to the closest color that can be displayed with `depth`.
+pub(crate) fn downsample(color: Color, depth: ColorDepth, quantization: Quantization) -> Color {
+    match (depth, color) {
+        (ColorDepth::Monochrome, _) => Color::Reset,
+        (ColorDepth::TrueColor, _) | (_, Color::Reset) => color,
+        (ColorDepth::Ansi256, Color::Rgb { r, g, b }) => {
+            // Only the color cube and the grayscale ramp are considered
+            // because terminals tend to customize the first 16 colors
+            let (value, _) = (16..=255)
+                .map(|value| {
+                    let distance = distance((r, g, b), ansi_value_to_rgb(value), quantization);
+                    (value, distance)
+                })
+                .min_by_key(|(_, distance)| *distance)
+                .unwrap();
+            Color::AnsiValue(value)
+        }
+        (ColorDepth::Ansi256, _) => color,
+        (ColorDepth::Ansi16, Color::AnsiValue(value)) if value < 16 => {
+            NAMED_COLORS[value as usize].0
+        }
+        (ColorDepth::Ansi16, Color::Rgb { .. }) | (ColorDepth::Ansi16, Color::AnsiValue(_)) => {
+            let rgb = to_rgb(color).unwrap();
+            NAMED_COLORS
+                .iter()
+                .min_by_key(|(_, named_rgb)| distance(rgb, *named_rgb, quantization))
+                .unwrap()
+                .0
+        }
+        (ColorDepth::Ansi16, _) => color,
+    }
+}
@@ -5,14 +5,27 @@
 //! Welcome to the top of the hanbun crate.
 //! [`Buffer`] should be of interest to you.
 
+mod color;
+mod pixel;
+mod shape;
+
+pub use color::{ColorDepth, MonochromeStyle, Quantization};
+pub use pixel::{Mosaic, PixelMode};
+pub use shape::{Connectivity, EdgeColors, FillRule};
+
 use crossterm::{
+    cursor::{MoveDown, MoveTo, MoveToColumn, MoveUp},
     queue,
-    style::{ResetColor, SetBackgroundColor, SetForegroundColor},
+    style::{ResetColor, SetAttributes, SetBackgroundColor, SetForegroundColor},
+    tty::IsTty,
 };
 use std::{
+    convert::TryFrom,
     fmt,
     io::{self, stdout, BufWriter, Write},
 };
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 /// Returned by [`size`] if querying the terminal size failed.
 #[derive(Debug)]
@@ -26,6 +39,54 @@ impl fmt::Display for TerminalSizeError {
 
 impl std::error::Error for TerminalSizeError {}
 
+/// An error that can occur while using hanbun.
+#[derive(Debug)]
+pub enum Error {
+    /// Writing to the output failed.
+    Io(io::Error),
+    /// Querying the terminal size failed.
+    TerminalSize(TerminalSizeError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(formatter, "Failed to write output: {}", error),
+            Error::TerminalSize(error) => error.fmt(formatter),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            Error::TerminalSize(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Error {
+        Error::Io(error)
+    }
+}
+
+impl From<crossterm::ErrorKind> for Error {
+    fn from(error: crossterm::ErrorKind) -> Error {
+        match error {
+            crossterm::ErrorKind::IoError(error) => Error::Io(error),
+            error => Error::Io(io::Error::other(error)),
+        }
+    }
+}
+
+impl From<TerminalSizeError> for Error {
+    fn from(error: TerminalSizeError) -> Error {
+        Error::TerminalSize(error)
+    }
+}
+
 /// Returns the terminal's width and height.
 ///
 /// # Examples
@@ -52,24 +113,192 @@ pub fn size() -> Result<(usize, usize), TerminalSizeError> {
 /// See [this list](https://docs.rs/crossterm/0.19.0/crossterm/style/enum.Color.html) for all available colors.
 pub type Color = crossterm::style::Color;
 
+/// A text attribute like [`Attribute::Bold`] or [`Attribute::Underlined`].
+///
+/// See [this list](https://docs.rs/crossterm/0.19.0/crossterm/style/enum.Attribute.html) for all available attributes.
+/// Not every terminal supports every attribute.
+pub type Attribute = crossterm::style::Attribute;
+
+/// A set of [`Attribute`]s.
+///
+/// # Examples
+///
+/// ```
+/// use hanbun::{Attribute, Attributes};
+///
+/// let attributes = Attributes::from(Attribute::Bold) | Attribute::Underlined;
+/// ```
+pub type Attributes = crossterm::style::Attributes;
+
+/// The state of a half block.
+///
+/// Every combination of an upper and a lower block is drawn as follows:
+///
+/// | Upper \\ Lower | `Empty` | `Uncolored` | `Colored` |
+/// |---|---|---|---|
+/// | `Empty` | [`Cell::char`] | `▄` | `▄` in the lower color |
+/// | `Uncolored` | `▀` | `█` | `▀` on the lower color |
+/// | `Colored` | `▀` in the upper color | `▄` on the upper color | `▀` in the upper color on the lower color |
+///
+/// If [`Cell::char`] is set, it's drawn on the colors of the blocks instead.
+///
+/// # Examples
+///
+/// ```
+/// use hanbun::{Block, Color, ColorDepth};
+///
+/// let blocks = [Block::Empty, Block::Uncolored, Block::Colored(Color::Red)];
+/// let mut buffer = hanbun::Buffer::with_writer(9, 1, '.', Vec::new());
+/// buffer.set_color_depth(ColorDepth::Ansi256);
+/// for (x, cell) in buffer.cells.iter_mut().enumerate() {
+///     cell.upper_block = blocks[x / 3];
+///     cell.lower_block = blocks[x % 3];
+///     if x != 0 {
+///         cell.char = None;
+///     }
+/// }
+///
+/// assert_eq!(
+///     buffer.render_to_string(),
+///     concat!(
+///         ".▄\x1b[38;5;9m▄",
+///         "\x1b[0m▀█\x1b[48;5;9m▀",
+///         "\x1b[38;5;9m\x1b[49m▀\x1b[39m\x1b[48;5;9m▄\x1b[38;5;9m\x1b[49m█",
+///         "\x1b[0m",
+///     )
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Block {
+    /// The half block is not set.
+    Empty,
+    /// The half block is set and drawn in the terminal's default color. See [`Buffer::set`].
+    Uncolored,
+    /// The half block is set and drawn in a color. See [`Buffer::color`].
+    Colored(Color),
+}
+
+impl Block {
+    /// Returns `true` if the half block is [`Block::Empty`].
+    pub fn is_empty(self) -> bool {
+        self == Block::Empty
+    }
+}
+
 /// Represents a terminal cell. Every cell has two blocks.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Cell {
     /// The upper block. It can be modified using [`Buffer::set`] and [`Buffer::color`].
-    pub upper_block: Option<Option<Color>>,
+    pub upper_block: Block,
     /// The lower block. It can be modified using [`Buffer::set`] and [`Buffer::color`].
-    pub lower_block: Option<Option<Color>>,
-    /// The character used if both [`Cell::upper_block`] and [`Cell::lower_block`] are [`None`].
+    pub lower_block: Block,
+    /// The character used if both [`Cell::upper_block`] and [`Cell::lower_block`] are [`Block::Empty`].
+    ///
+    /// If a block isn't empty, this character is drawn on top of the blocks
+    /// (see [`Buffer::overlay_print`]).
     ///
     /// This character occupies the whole cell.
     pub char: Option<char>,
+    /// The rest of the grapheme that starts with [`Cell::char`], if any.
+    ///
+    /// These are characters like combining accents or the remaining parts of an emoji sequence
+    /// that are drawn together with [`Cell::char`] in a single cell.
+    pub combining: Option<String>,
     /// A color for [`Cell::char`].
     pub char_color: Option<Color>,
+    /// A background color for [`Cell::char`].
+    pub char_background: Option<Color>,
+    /// Text attributes for [`Cell::char`].
+    pub char_attributes: Attributes,
+    /// Whether [`Cell::char`] was printed rather than being the character the buffer was cleared with.
+    ///
+    /// Printed cells are never filled by [`Buffer::flood_fill`].
+    pub printed: bool,
+    /// Whether this cell is covered by a wide character like `日` in the cell to its left.
+    ///
+    /// Wide characters are followed by one of these cells because they occupy two terminal cells.
+    /// Such a cell is not drawn.
+    pub continuation: bool,
+    /// The pixels of the cell if they were drawn with a [`PixelMode`] other than [`PixelMode::HalfBlocks`].
+    ///
+    /// If this is set, it's drawn instead of [`Cell::upper_block`] and [`Cell::lower_block`],
+    /// which then approximate the pixels.
+    pub mosaic: Option<Mosaic>,
+}
+
+impl Cell {
+    /// Returns `true` if this cell is covered by a wide character like `日` in the cell to its left.
+    ///
+    /// See [`Cell::continuation`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut buffer = hanbun::Buffer::with_writer(3, 1, '.', Vec::new());
+    /// buffer.print(0, 0, "日");
+    /// assert!(buffer.cells[1].is_continuation());
+    ///
+    /// // A cell with nothing in it is drawn as a space
+    /// buffer.cells[2].char = None;
+    /// assert!(!buffer.cells[2].is_continuation());
+    /// assert_eq!(buffer.render_to_string(), "日 ");
+    /// ```
+    pub fn is_continuation(&self) -> bool {
+        self.continuation
+    }
+
+    /// Returns `true` if this cell has no half blocks and no character.
+    fn is_blank(&self) -> bool {
+        self.char.is_none() && self.upper_block.is_empty() && self.lower_block.is_empty()
+    }
+
+    /// Sets the pixel at `column` and `row` of `mode` to `block`.
+    fn put(&mut self, mode: PixelMode, column: usize, row: usize, block: Block) {
+        if mode == PixelMode::HalfBlocks && self.mosaic.is_none() {
+            if row == 0 {
+                self.upper_block = block;
+            } else {
+                self.lower_block = block;
+            }
+            return;
+        }
+
+        let mut mosaic = self
+            .mosaic
+            .unwrap_or_else(|| Mosaic::from_halves(self.upper_block, self.lower_block));
+        mosaic.put(mode, column, row, block);
+        (self.upper_block, self.lower_block) = mosaic.halves();
+        self.mosaic = Some(mosaic).filter(|mosaic| !mosaic.is_empty());
+    }
+
+    /// Returns the pixel at `column` and `row` of `mode`.
+    fn pixel(&self, mode: PixelMode, column: usize, row: usize) -> Block {
+        self.mosaic
+            .unwrap_or_else(|| Mosaic::from_halves(self.upper_block, self.lower_block))
+            .get(mode, column, row)
+    }
+}
+
+/// Where a [`Buffer`] is drawn on the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Origin {
+    /// The buffer is drawn where the cursor is and its rows are separated by newlines.
+    ///
+    /// This is the default.
+    Cursor,
+    /// The buffer is drawn with its top-left cell at `column` and `row` of the terminal
+    /// and the cursor is moved to every row explicitly.
+    ///
+    /// Use `Origin::Position { column: 0, row: 0 }` to draw at the top-left of the terminal.
+    Position { column: u16, row: u16 },
 }
 
 /// A buffer for storing the state of the cells.
 /// You can see it as a drawing canvas.
 ///
+/// The buffer is drawn to `W`, which is [`io::Stdout`] by default.
+/// Use [`Buffer::with_writer`] to draw somewhere else.
+///
 /// # Examples
 ///
 /// ```
@@ -84,27 +313,74 @@ pub struct Cell {
 /// buffer.set(3, 3);
 /// buffer.draw();
 /// ```
-pub struct Buffer {
+pub struct Buffer<W: Write = io::Stdout> {
     pub cells: Vec<Cell>,
     pub width: usize,
     pub height: usize,
-    writer: BufWriter<io::Stdout>,
+    writer: BufWriter<W>,
+    origin: Origin,
+    color_depth: ColorDepth,
+    quantization: Quantization,
+    monochrome_style: MonochromeStyle,
+    pixel_mode: PixelMode,
+    legacy_symbols: bool,
+    /// The cells as they were at the last draw.
+    drawn_cells: Option<Vec<Cell>>,
+    /// Whether the last successful draw was with [`Origin::Cursor`], which left the cursor at the end of its last row.
+    drawn_at_cursor: bool,
+    /// The cell the buffer was last cleared with. Cells fall back to it when they have nothing left.
+    cleared_cell: Cell,
 }
 
 impl Buffer {
     /// Creates a new buffer of `width * height` cells filled with `char`.
+    ///
+    /// The color depth is detected using [`ColorDepth::detect`].
+    /// If stdout is not a terminal, the buffer is drawn with [`ColorDepth::Monochrome`].
     pub fn new(width: usize, height: usize, char: char) -> Buffer {
+        let stdout = stdout();
+        let is_tty = stdout.is_tty();
+        let mut buffer = Buffer::with_writer(width, height, char, stdout);
+        buffer.color_depth = if is_tty {
+            ColorDepth::detect()
+        } else {
+            ColorDepth::Monochrome
+        };
+        buffer
+    }
+}
+
+impl<W: Write> Buffer<W> {
+    /// Creates a new buffer of `width * height` cells filled with `char` that is drawn to `writer`.
+    ///
+    /// Unlike [`Buffer::new`], this doesn't look at the environment:
+    /// the buffer is drawn with [`ColorDepth::TrueColor`] until [`Buffer::set_color_depth`] is called,
+    /// so the output is the same everywhere.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut buffer = hanbun::Buffer::with_writer(3, 1, ' ', Vec::new());
+    ///
+    /// buffer.print(0, 0, "abc");
+    /// buffer.draw();
+    ///
+    /// assert_eq!(buffer.writer(), b"abc");
+    /// ```
+    pub fn with_writer(width: usize, height: usize, char: char, writer: W) -> Buffer<W> {
+        let cleared_cell = cleared_cell(char, None);
         Buffer {
-            cells: vec![
-                Cell {
-                    upper_block: None,
-                    lower_block: None,
-                    char: Some(char),
-                    char_color: None
-                };
-                width * height
-            ],
-            writer: BufWriter::with_capacity(width * height, stdout()),
+            cells: vec![cleared_cell.clone(); width * height],
+            writer: BufWriter::with_capacity(width * height, writer),
+            origin: Origin::Cursor,
+            color_depth: ColorDepth::TrueColor,
+            quantization: Quantization::Perceptual,
+            monochrome_style: MonochromeStyle::Blocks,
+            pixel_mode: PixelMode::HalfBlocks,
+            legacy_symbols: true,
+            drawn_cells: None,
+            drawn_at_cursor: false,
+            cleared_cell,
             width,
             height,
         }
@@ -112,199 +388,2078 @@ impl Buffer {
 
     /// Draws the buffer to the screen.
     ///
+    /// Only the cells that changed since the last draw are written.
+    /// The first draw writes every cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::ColorDepth;
+    ///
+    /// let mut buffer = hanbun::Buffer::with_writer(4, 2, '.', Vec::new());
+    /// buffer.set_color_depth(ColorDepth::Ansi16);
+    /// buffer.draw();
+    /// assert_eq!(buffer.writer(), "....\n....".as_bytes());
+    ///
+    /// buffer.writer_mut().clear();
+    /// buffer.set(1, 0);
+    /// buffer.draw();
+    /// // Only the changed cell is written, after moving the cursor to it and back
+    /// assert_eq!(buffer.writer(), "\x1b[1A\x1b[2G▀\x1b[1B\x1b[5G".as_bytes());
+    ///
+    /// buffer.writer_mut().clear();
+    /// buffer.draw();
+    /// assert_eq!(buffer.writer(), b"");
+    /// ```
+    ///
     /// # Panics
     ///
     /// Panics if an internal write operation operation failed.
+    /// Use [`Buffer::try_draw`] to handle this case.
     pub fn draw(&mut self) {
-        let writer = &mut self.writer;
-        let mut x = 0;
-        let mut y = 1;
-        for cell in &self.cells {
-            // NOTE: This can be improved after https://github.com/rust-lang/rust/issues/53667
-            if cell.upper_block.is_some() && cell.lower_block.is_some() {
-                if let Some(Some(upper_color)) = cell.upper_block {
-                    if let Some(Some(lower_color)) = cell.lower_block {
-                        queue!(writer, SetForegroundColor(upper_color)).unwrap();
-                        queue!(writer, SetBackgroundColor(lower_color)).unwrap();
-                        writer.write_all("▀".as_bytes()).unwrap();
-                    } else {
-                        queue!(writer, SetBackgroundColor(upper_color)).unwrap();
-                        writer.write_all("▄".as_bytes()).unwrap();
-                    }
-                    queue!(writer, ResetColor).unwrap();
-                } else if let Some(Some(lower_color)) = cell.lower_block {
-                    if let Some(Some(upper_color)) = cell.upper_block {
-                        queue!(writer, SetForegroundColor(upper_color)).unwrap();
-                        queue!(writer, SetBackgroundColor(lower_color)).unwrap();
-                        writer.write_all("▀".as_bytes()).unwrap();
-                    } else {
-                        queue!(writer, SetBackgroundColor(lower_color)).unwrap();
-                        writer.write_all("▀".as_bytes()).unwrap();
-                    }
-                    queue!(writer, ResetColor).unwrap();
-                } else {
-                    writer.write_all("█".as_bytes()).unwrap();
-                }
-            } else if let Some(upper_block) = cell.upper_block {
-                if let Some(color) = upper_block {
-                    queue!(writer, SetForegroundColor(color)).unwrap();
-                }
-                writer.write_all("▀".as_bytes()).unwrap();
-                if upper_block.is_some() {
-                    queue!(writer, ResetColor).unwrap();
+        self.try_draw()
+            .unwrap_or_else(|error| panic!("drawing buffer ({})", error));
+    }
+
+    /// Draws the buffer to the screen like [`Buffer::draw`] but returns an error instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut buffer = hanbun::Buffer::new(5, 5, ' ');
+    ///
+    /// buffer.set(3, 3);
+    /// if let Err(error) = buffer.try_draw() {
+    ///     eprintln!("{}", error);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if writing to the output failed.
+    /// In that case, the next draw draws every cell.
+    pub fn try_draw(&mut self) -> Result<(), Error> {
+        // If we fail halfway, we don't know what's on the screen, so this stays `None`
+        let drawn_cells = self.drawn_cells.take();
+        let is_monochrome = self.color_depth == ColorDepth::Monochrome;
+        match &drawn_cells {
+            // Moving the cursor to the changed cells needs escape sequences,
+            // so monochrome buffers are drawn again in full on the next rows instead
+            Some(drawn_cells) if is_monochrome && self.origin == Origin::Cursor => {
+                if *drawn_cells != self.cells {
+                    self.draw_all_cells()?;
                 }
-            } else if let Some(lower_block) = cell.lower_block {
-                if let Some(color) = lower_block {
-                    queue!(writer, SetForegroundColor(color)).unwrap();
+            }
+            Some(drawn_cells) if drawn_cells.len() == self.cells.len() => {
+                self.draw_changes(drawn_cells)?
+            }
+            _ => self.draw_all_cells()?,
+        }
+        self.writer.flush()?;
+
+        self.drawn_at_cursor = self.origin == Origin::Cursor;
+        let mut drawn_cells = drawn_cells.unwrap_or_default();
+        drawn_cells.clone_from(&self.cells);
+        self.drawn_cells = Some(drawn_cells);
+        Ok(())
+    }
+
+    /// Returns a reference to the writer the buffer is drawn to.
+    pub fn writer(&self) -> &W {
+        self.writer.get_ref()
+    }
+
+    /// Returns a mutable reference to the writer the buffer is drawn to.
+    ///
+    /// If you write to it, you probably want to call [`Buffer::invalidate`] too.
+    pub fn writer_mut(&mut self) -> &mut W {
+        self.writer.get_mut()
+    }
+
+    /// Makes the next [`Buffer::draw`] draw every cell, including the ones that didn't change.
+    ///
+    /// Use this if something else has been written to the terminal since the last draw.
+    /// With [`Origin::Cursor`], the buffer is drawn again where the last draw drew it,
+    /// so the cursor is expected to be where the last draw left it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::ColorDepth;
+    ///
+    /// let mut buffer = hanbun::Buffer::with_writer(2, 2, '.', Vec::new());
+    /// buffer.set_color_depth(ColorDepth::Ansi16);
+    /// buffer.draw();
+    ///
+    /// buffer.writer_mut().clear();
+    /// buffer.invalidate();
+    /// buffer.draw();
+    /// // The cursor is moved back to the top-left cell first
+    /// assert_eq!(buffer.writer(), "\x1b[1A\x1b[1G..\n..".as_bytes());
+    /// ```
+    pub fn invalidate(&mut self) {
+        self.drawn_cells = None;
+    }
+
+    /// Sets where the buffer is drawn. See [`Origin`].
+    ///
+    /// The next [`Buffer::draw`] draws every cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::{ColorDepth, Origin};
+    ///
+    /// let mut buffer = hanbun::Buffer::with_writer(2, 2, '.', Vec::new());
+    /// buffer.set_color_depth(ColorDepth::Ansi16);
+    /// buffer.set_origin(Origin::Position { column: 2, row: 3 });
+    /// buffer.draw();
+    /// // Terminal rows and columns are 1-based in escape sequences
+    /// assert_eq!(buffer.writer(), "\x1b[4;3H..\x1b[5;3H..".as_bytes());
+    ///
+    /// buffer.writer_mut().clear();
+    /// buffer.set(1, 2);
+    /// buffer.draw();
+    /// assert_eq!(buffer.writer(), "\x1b[5;4H▀".as_bytes());
+    ///
+    /// // Positions past the largest terminal position are clamped to it
+    /// buffer.set_origin(Origin::Position { column: u16::MAX, row: u16::MAX });
+    /// buffer.draw();
+    /// ```
+    pub fn set_origin(&mut self, origin: Origin) {
+        self.origin = origin;
+        self.drawn_at_cursor = false;
+        self.invalidate();
+    }
+
+    /// Returns what [`Buffer::draw`] would write if it drew every cell, including escape sequences.
+    ///
+    /// Nothing is written to the buffer's writer.
+    /// The escape sequences depend on the buffer's color depth, see [`Buffer::set_color_depth`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::Color;
+    ///
+    /// let mut buffer = hanbun::Buffer::new(3, 1, ' ');
+    /// buffer.color(1, 0, Color::Red);
+    ///
+    /// println!("Look at this: {}", buffer.render_to_string());
+    /// ```
+    pub fn render_to_string(&self) -> String {
+        let mut bytes = Vec::with_capacity(self.cells.len());
+        // Writing to a `Vec` can't fail
+        let mut style = self.terminal_style();
+        draw_all(&mut bytes, &self.cells, self.width, self.origin, &mut style).unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    /// Returns the buffer as plain text without any colors or escape sequences.
+    ///
+    /// Rows are separated by newlines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut buffer = hanbun::Buffer::new(3, 2, '.');
+    /// buffer.set(0, 0);
+    /// buffer.set(0, 1);
+    /// buffer.set(1, 1);
+    /// buffer.print(1, 2, "hi");
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "█▄.\n.hi");
+    /// ```
+    pub fn render_to_plain_string(&self) -> String {
+        let mut string = String::with_capacity(self.cells.len() + self.height);
+        if self.width == 0 {
+            return string;
+        }
+
+        for (y, cells) in self.cells.chunks(self.width).enumerate() {
+            if y != 0 {
+                string.push('\n');
+            }
+            for cell in cells {
+                if cell.is_continuation() {
+                    continue;
                 }
-                writer.write_all("▄".as_bytes()).unwrap();
-                if lower_block.is_some() {
-                    queue!(writer, ResetColor).unwrap();
+                string.push(plain_char(cell, self.legacy_symbols));
+                if let Some(combining) = &cell.combining {
+                    string.push_str(combining);
                 }
-            } else if let Some(char) = &cell.char {
-                if let Some(color) = cell.char_color {
-                    queue!(writer, SetForegroundColor(color)).unwrap();
+            }
+        }
+        string
+    }
+
+    /// Sets how many colors the terminal can display. See [`ColorDepth`].
+    ///
+    /// By default, this is detected using [`ColorDepth::detect`] for [`Buffer::new`]
+    /// and [`ColorDepth::TrueColor`] for [`Buffer::with_writer`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::{Color, ColorDepth};
+    ///
+    /// let mut buffer = hanbun::Buffer::with_writer(1, 1, ' ', Vec::new());
+    /// buffer.set_color_depth(ColorDepth::Ansi16);
+    /// buffer.color(0, 0, Color::Rgb { r: 250, g: 10, b: 10 });
+    ///
+    /// // This is drawn using `Color::Red`
+    /// buffer.draw();
+    /// assert_eq!(buffer.writer(), "\x1b[38;5;9m▀\x1b[0m".as_bytes());
+    /// ```
+    ///
+    /// With [`ColorDepth::Monochrome`], no escape sequences are written at all,
+    /// so the output can be written to a file:
+    ///
+    /// ```
+    /// use hanbun::ColorDepth;
+    ///
+    /// let mut buffer = hanbun::Buffer::with_writer(4, 2, '.', Vec::new());
+    /// buffer.set_color_depth(ColorDepth::Monochrome);
+    /// buffer.draw();
+    /// buffer.set(1, 0);
+    /// buffer.draw();
+    /// buffer.draw();
+    ///
+    /// assert!(!buffer.writer().contains(&b'\x1b'));
+    /// assert_eq!(buffer.writer(), "....\n....\n.▀..\n....".as_bytes());
+    /// ```
+    pub fn set_color_depth(&mut self, color_depth: ColorDepth) {
+        self.color_depth = color_depth;
+        self.invalidate();
+    }
+
+    /// Sets how colored cells are displayed with [`ColorDepth::Monochrome`]. See [`MonochromeStyle`].
+    ///
+    /// By default, this is [`MonochromeStyle::Blocks`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::{Color, ColorDepth, MonochromeStyle};
+    ///
+    /// let mut buffer = hanbun::Buffer::with_writer(2, 1, ' ', Vec::new());
+    /// buffer.set_color_depth(ColorDepth::Monochrome);
+    /// buffer.set_monochrome_style(MonochromeStyle::Shades);
+    /// buffer.set(0, 0);
+    /// buffer.set(0, 1);
+    /// buffer.color(1, 0, Color::DarkGrey);
+    ///
+    /// assert_eq!(buffer.render_to_string(), "█░");
+    /// ```
+    pub fn set_monochrome_style(&mut self, monochrome_style: MonochromeStyle) {
+        self.monochrome_style = monochrome_style;
+        self.invalidate();
+    }
+
+    /// Sets how colors are mapped to the ones the terminal can display. See [`Quantization`].
+    ///
+    /// By default, this is [`Quantization::Perceptual`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::{Color, ColorDepth, Quantization};
+    ///
+    /// let mut buffer = hanbun::Buffer::with_writer(1, 1, ' ', Vec::new());
+    /// buffer.set_color_depth(ColorDepth::Ansi16);
+    /// buffer.color(0, 0, Color::Rgb { r: 100, g: 0, b: 100 });
+    ///
+    /// // This dark purple looks closest to `Color::Black`
+    /// assert_eq!(buffer.render_to_string(), "\x1b[38;5;0m▀\x1b[0m");
+    ///
+    /// // but is closest to `Color::DarkGrey` by plain RGB distance
+    /// buffer.set_quantization(Quantization::Nearest);
+    /// assert_eq!(buffer.render_to_string(), "\x1b[38;5;8m▀\x1b[0m");
+    /// ```
+    pub fn set_quantization(&mut self, quantization: Quantization) {
+        self.quantization = quantization;
+        self.invalidate();
+    }
+
+    /// Sets whether the terminal's font has the Symbols for Legacy Computing
+    /// that [`PixelMode::Sextants`] and [`PixelMode::Octants`] are drawn with.
+    ///
+    /// If it doesn't, cells drawn with these modes are drawn as half blocks instead.
+    ///
+    /// By default, this is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::PixelMode;
+    ///
+    /// let mut buffer = hanbun::Buffer::new(1, 1, ' ');
+    /// buffer.set_pixel_mode(PixelMode::Sextants);
+    /// buffer.set(0, 0);
+    /// buffer.set(1, 2);
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "\u{1FB1F}");
+    ///
+    /// buffer.set_legacy_symbols(false);
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "█");
+    /// ```
+    pub fn set_legacy_symbols(&mut self, legacy_symbols: bool) {
+        self.legacy_symbols = legacy_symbols;
+        self.invalidate();
+    }
+
+    /// Sets how [`Buffer::set`], [`Buffer::color`], [`Buffer::unset`] and [`Buffer::get`]
+    /// divide cells into pixels. See [`PixelMode`].
+    ///
+    /// This only affects the pixels drawn from now on,
+    /// so different parts of the buffer can be drawn with different modes.
+    /// Printing is not affected.
+    ///
+    /// By default, this is [`PixelMode::HalfBlocks`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::PixelMode;
+    ///
+    /// let mut buffer = hanbun::Buffer::new(2, 1, ' ');
+    /// buffer.set_pixel_mode(PixelMode::Quadrants);
+    /// // The buffer is now 4 pixels wide and 2 pixels high
+    /// for (x, y) in [(0, 0), (1, 1), (2, 0), (2, 1), (3, 1)] {
+    ///     buffer.set(x, y);
+    /// }
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "▚▙");
+    /// ```
+    pub fn set_pixel_mode(&mut self, pixel_mode: PixelMode) {
+        self.pixel_mode = pixel_mode;
+    }
+
+    /// Returns how many pixels wide and high the buffer is in the current [`PixelMode`].
+    ///
+    /// With [`PixelMode::HalfBlocks`], this is `(width, height * 2)`.
+    pub fn pixel_size(&self) -> (usize, usize) {
+        let (columns, rows) = self.pixel_mode.size();
+        (self.width * columns, self.height * rows)
+    }
+
+    /// Returns the range of pixels in the current [`PixelMode`].
+    fn pixel_bounds(&self) -> shape::Bounds {
+        let (pixel_width, pixel_height) = self.pixel_size();
+        shape::Bounds {
+            left: 0,
+            top: 0,
+            right: pixel_width as i64 - 1,
+            bottom: pixel_height as i64 - 1,
+        }
+    }
+
+    fn terminal_style(&self) -> TerminalStyle {
+        TerminalStyle::new(
+            self.color_depth,
+            self.quantization,
+            self.monochrome_style,
+            self.legacy_symbols,
+        )
+    }
+
+    /// Draws every cell.
+    ///
+    /// With [`Origin::Cursor`], the cells are drawn over the last drawn ones, if any.
+    /// Monochrome buffers can't move the cursor, so they are drawn on the next rows instead.
+    fn draw_all_cells(&mut self) -> crossterm::Result<()> {
+        if self.drawn_at_cursor && self.origin == Origin::Cursor && !self.cells.is_empty() {
+            if self.color_depth == ColorDepth::Monochrome {
+                self.writer.write_all(b"\n")?;
+            } else {
+                if self.height > 1 {
+                    let rows = u16::try_from(self.height - 1).unwrap_or(u16::MAX);
+                    queue!(self.writer, MoveUp(rows))?;
                 }
+                queue!(self.writer, MoveToColumn(1))?;
+            }
+        }
+
+        let mut style = self.terminal_style();
+        draw_all(
+            &mut self.writer,
+            &self.cells,
+            self.width,
+            self.origin,
+            &mut style,
+        )
+    }
 
-                write!(writer, "{}", char).unwrap();
-                if cell.char_color.is_some() {
-                    queue!(writer, ResetColor).unwrap();
+    /// Draws only the cells that differ from `drawn_cells`.
+    ///
+    /// With [`Origin::Cursor`], the cursor is expected to be on the last row, where the previous draw left it.
+    fn draw_changes(&mut self, drawn_cells: &[Cell]) -> crossterm::Result<()> {
+        let mut style = self.terminal_style();
+        let writer = &mut self.writer;
+        let width = self.width;
+        let last_row = self.height.saturating_sub(1);
+        let mut cursor_y = last_row;
+        // The position right after the last cell we drew, if any
+        let mut next_position = None;
+        for (index, (cell, drawn_cell)) in self.cells.iter().zip(drawn_cells).enumerate() {
+            // Continuation cells are drawn as part of the wide character to their left
+            if cell == drawn_cell || cell.is_continuation() {
+                continue;
+            }
+
+            let x = index % width;
+            let y = index / width;
+            if next_position != Some((x, y)) {
+                match self.origin {
+                    Origin::Cursor => {
+                        if y < cursor_y {
+                            queue!(writer, MoveUp((cursor_y - y) as u16))?;
+                        } else if y > cursor_y {
+                            queue!(writer, MoveDown((y - cursor_y) as u16))?;
+                        }
+                        cursor_y = y;
+                        // Columns are 1-based here
+                        queue!(writer, MoveToColumn(terminal_offset(1, x)))?;
+                    }
+                    Origin::Position { column, row } => {
+                        queue!(
+                            writer,
+                            MoveTo(terminal_offset(column, x), terminal_offset(row, y))
+                        )?;
+                    }
                 }
-            } else {
-                unreachable!();
             }
 
-            x += 1;
-            if y != self.height && x == self.width {
-                writer.write_all(b"\n").unwrap();
-                x = 0;
-                y += 1;
+            draw_cell(writer, cell, &mut style)?;
+            let is_wide = x + 1 != width && self.cells[index + 1].is_continuation();
+            next_position = Some((x + if is_wide { 2 } else { 1 }, y));
+        }
+
+        style.reset(writer)?;
+
+        if next_position.is_some() && self.origin == Origin::Cursor {
+            // Leave the cursor where a full draw would have left it
+            if cursor_y < last_row {
+                queue!(writer, MoveDown((last_row - cursor_y) as u16))?;
             }
+            queue!(writer, MoveToColumn(terminal_offset(1, width)))?;
         }
-        self.writer.flush().unwrap();
+        Ok(())
     }
 
     /// Clears the buffer using `char`.
     pub fn clear(&mut self, char: char) {
-        self.cells.fill(Cell {
-            upper_block: None,
-            lower_block: None,
-            char: Some(char),
-            char_color: None,
-        })
+        self.cleared_cell = cleared_cell(char, None);
+        self.cells.fill(self.cleared_cell.clone())
     }
 
     /// Clears the buffer using `char` colored with `color`.
     pub fn colored_clear(&mut self, char: char, color: Color) {
-        self.cells.fill(Cell {
-            upper_block: None,
-            lower_block: None,
-            char: Some(char),
-            char_color: Some(color),
-        })
+        self.cleared_cell = cleared_cell(char, Some(color));
+        self.cells.fill(self.cleared_cell.clone())
     }
 
-    /// Sets the cell at (`x`, `y`) to a half block.
+    /// Returns the half block at (`x`, `y`) or [`None`] if (`x`, `y`) is out of the buffer's range.
     ///
-    /// # Panics
+    /// This uses the same coordinates as [`Buffer::set`] and [`Buffer::color`],
+    /// so with a [`PixelMode`] other than [`PixelMode::HalfBlocks`] this returns the pixel of that mode.
     ///
-    /// Panics if (`x`, `y`) is out of the buffer's range.
-    pub fn set(&mut self, x: usize, y: usize) {
-        let position = x + self.width * (y / 2);
-        let current_cell = &self
-            .cells
-            .get(position)
-            .unwrap_or_else(|| panic!("setting block at ({}, {}) (out of range)", x, y));
-
-        if y % 2 == 0 {
-            self.cells[position] = Cell {
-                upper_block: Some(None),
-                lower_block: current_cell.lower_block,
-                char: None,
-                char_color: None,
-            };
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::{Block, Color};
+    ///
+    /// let mut buffer = hanbun::Buffer::new(2, 1, ' ');
+    /// buffer.color(1, 1, Color::Red);
+    ///
+    /// assert_eq!(buffer.get(1, 1), Some(Block::Colored(Color::Red)));
+    /// assert_eq!(buffer.get(1, 0), Some(Block::Empty));
+    /// assert_eq!(buffer.get(1, 2), None);
+    /// assert_eq!(buffer.get(0, usize::MAX), None);
+    /// ```
+    pub fn get(&self, x: usize, y: usize) -> Option<Block> {
+        let (columns, rows) = self.pixel_mode.size();
+        let cell = self.cell(x / columns, y / rows)?;
+        Some(cell.pixel(self.pixel_mode, x % columns, y % rows))
+    }
+
+    /// Returns the cell in `column` and `row` or [`None`] if it's out of the buffer's range.
+    ///
+    /// Unlike [`Buffer::get`], this uses the coordinates of terminal cells.
+    pub fn cell(&self, column: usize, row: usize) -> Option<&Cell> {
+        if column < self.width && row < self.height {
+            self.cells.get(column + self.width * row)
         } else {
-            self.cells[position] = Cell {
-                upper_block: current_cell.upper_block,
-                lower_block: Some(None),
-                char: None,
-                char_color: None,
-            };
+            None
         }
     }
 
-    /// Colors the cell at (`x`, `y`) with `color`.
-    ///
-    /// # Panics
-    ///
-    /// Panics if (`x`, `y`) is out of the buffer's range.
-    pub fn color(&mut self, x: usize, y: usize, color: Color) {
-        let position = x + self.width * (y / 2);
-        let current_cell = &self
-            .cells
-            .get(position)
-            .unwrap_or_else(|| panic!("coloring block at ({}, {}) (out of range)", x, y));
-
-        if y % 2 == 0 {
-            self.cells[position] = Cell {
-                upper_block: Some(Some(color)),
-                lower_block: current_cell.lower_block,
-                char: None,
-                char_color: None,
-            };
+    /// Returns the cell in `column` and `row` mutably or [`None`] if it's out of the buffer's range.
+    pub fn cell_mut(&mut self, column: usize, row: usize) -> Option<&mut Cell> {
+        if column < self.width && row < self.height {
+            self.cells.get_mut(column + self.width * row)
         } else {
-            self.cells[position] = Cell {
-                upper_block: current_cell.upper_block,
-                lower_block: Some(Some(color)),
-                char: None,
-                char_color: None,
-            };
+            None
         }
     }
 
-    /// Prints `string` to (`x`, `y`).
+    /// Sets the cell at (`x`, `y`) to a half block.
+    ///
+    /// Use [`Buffer::set_clipped`] if (`x`, `y`) might be out of range.
     ///
     /// # Panics
     ///
     /// Panics if (`x`, `y`) is out of the buffer's range.
-    pub fn print(&mut self, x: usize, y: usize, string: &str) {
-        let position = x + self.width * (y / 2);
-
-        for (index, char) in string.chars().enumerate() {
-            let cell = self
-                .cells
-                .get_mut(index + position)
-                .unwrap_or_else(|| panic!("printing at ({}, {}) (out of range)", x, y));
+    pub fn set(&mut self, x: usize, y: usize) {
+        self.put(x, y, Block::Uncolored, "setting");
+    }
 
-            *cell = Cell {
-                upper_block: None,
-                lower_block: None,
-                char: Some(char),
-                char_color: None,
-            };
+    /// Sets the cell at (`x`, `y`) to a half block if (`x`, `y`) is in the buffer's range.
+    ///
+    /// Returns `true` if the half block was clipped because it's out of range.
+    pub fn set_clipped(&mut self, x: usize, y: usize) -> bool {
+        if self.contains_pixel(x, y) {
+            self.set(x, y);
+            false
+        } else {
+            true
         }
     }
 
-    /// Prints a colored `string` to (`x`, `y`) with `color`.
+    /// Sets the cell at the signed (`x`, `y`) to a half block if it's in the buffer's range.
     ///
-    /// # Panics
+    /// This allows drawing things that are partially off the buffer,
+    /// such as objects sliding in from the left or the top.
+    ///
+    /// Returns `true` if the half block was clipped because it's out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut buffer = hanbun::Buffer::new(3, 1, ' ');
+    /// for x in -2..1 {
+    ///     buffer.set_at(x, 0);
+    ///     buffer.set_at(x, -1);
+    /// }
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "▀  ");
+    /// ```
+    pub fn set_at(&mut self, x: i32, y: i32) -> bool {
+        if let Some((x, y)) = self.unsigned(x, y) {
+            self.set(x, y);
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Turns the half block at (`x`, `y`) off again.
+    ///
+    /// If the cell has no half blocks left, it falls back to its character.
+    /// If it has no character either, it falls back to the character the buffer was created
+    /// or last cleared with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut buffer = hanbun::Buffer::new(2, 1, '.');
+    /// buffer.set(0, 0);
+    /// buffer.set(0, 1);
+    /// buffer.set(1, 0);
+    /// buffer.unset(0, 0);
+    /// buffer.unset(1, 0);
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "▄.");
+    /// ```
+    ///
+    /// Cells without half blocks, like the ones of printed text, are kept as they are:
+    ///
+    /// ```
+    /// let mut buffer = hanbun::Buffer::with_writer(4, 1, '.', Vec::new());
+    /// buffer.print(0, 0, "日");
+    /// buffer.unset(0, 0);
+    /// buffer.unset(1, 0);
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "日..");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if (`x`, `y`) is out of the buffer's range.
+    pub fn unset(&mut self, x: usize, y: usize) {
+        self.put(x, y, Block::Empty, "unsetting");
+    }
+
+    /// Turns the half block at the signed (`x`, `y`) off again if it's in the buffer's range.
+    ///
+    /// Returns `true` if the half block was clipped because it's out of range.
+    pub fn unset_at(&mut self, x: i32, y: i32) -> bool {
+        if let Some((x, y)) = self.unsigned(x, y) {
+            self.unset(x, y);
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Colors the cell at (`x`, `y`) with `color`.
+    ///
+    /// Use [`Buffer::color_clipped`] if (`x`, `y`) might be out of range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if (`x`, `y`) is out of the buffer's range.
+    pub fn color(&mut self, x: usize, y: usize, color: Color) {
+        self.put(x, y, Block::Colored(color), "coloring");
+    }
+
+    /// Colors the cell at (`x`, `y`) with `color` if (`x`, `y`) is in the buffer's range.
+    ///
+    /// Returns `true` if the half block was clipped because it's out of range.
+    pub fn color_clipped(&mut self, x: usize, y: usize, color: Color) -> bool {
+        if self.contains_pixel(x, y) {
+            self.color(x, y, color);
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Colors the cell at the signed (`x`, `y`) with `color` if it's in the buffer's range.
+    ///
+    /// Returns `true` if the half block was clipped because it's out of range.
+    pub fn color_at(&mut self, x: i32, y: i32, color: Color) -> bool {
+        if let Some((x, y)) = self.unsigned(x, y) {
+            self.color(x, y, color);
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Draws a line from the signed (`x0`, `y0`) to (`x1`, `y1`) with `color`.
+    ///
+    /// The coordinates are the same as for [`Buffer::set`] and [`Buffer::color`]
+    /// and everything outside of the buffer is clipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::Color;
+    ///
+    /// let mut buffer = hanbun::Buffer::new(6, 2, ' ');
+    /// buffer.line(0, 0, 5, 3, Color::Green);
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "▀▄▄   \n   ▀▀▄");
+    ///
+    /// // Any coordinates can be used
+    /// buffer.clear(' ');
+    /// buffer.line(i32::MIN, i32::MIN, i32::MAX, i32::MAX, Color::Green);
+    /// assert_eq!(buffer.render_to_plain_string(), "▀▄    \n  ▀▄  ");
+    /// ```
+    pub fn line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: Color) {
+        self.thick_line(x0, y0, x1, y1, 1, color);
+    }
+
+    /// Draws a line that is `thickness` pixels thick from the signed (`x0`, `y0`) to (`x1`, `y1`) with `color`.
+    ///
+    /// A `thickness` of 0 draws nothing. See [`Buffer::line`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::Color;
+    ///
+    /// let mut buffer = hanbun::Buffer::new(4, 2, ' ');
+    /// // Only the part of the line in the buffer is drawn
+    /// buffer.thick_line(-1_000_000_000, 1, 1_000_000_000, 1, 2, Color::Red);
+    /// buffer.thick_line(0, 3, 3, 3, 0, Color::Red);
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "▄▄▄▄\n▀▀▀▀");
+    ///
+    /// // Any thickness can be used
+    /// buffer.thick_line(0, 0, 3, 3, u32::MAX, Color::Red);
+    /// assert_eq!(buffer.render_to_plain_string(), "████\n████");
+    /// ```
+    pub fn thick_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, thickness: u32, color: Color) {
+        let bounds = self.pixel_bounds();
+        // Dots centered outside of the buffer can still reach into it
+        let line_bounds = bounds.grown(thickness as i64 / 2);
+        let (x0, y0, x1, y1) = (x0 as i64, y0 as i64, x1 as i64, y1 as i64);
+        shape::line(x0, y0, x1, y1, line_bounds, |x, y| {
+            shape::dot(x, y, thickness, bounds, |x, y| {
+                self.plot(x, y, Block::Colored(color));
+            });
+        });
+    }
+
+    /// Draws the outline of a rectangle `width` pixels wide and `height` pixels high
+    /// with its top-left corner at the signed (`x`, `y`) with `color`.
+    ///
+    /// The coordinates are the same as for [`Buffer::set`] and [`Buffer::color`]
+    /// and everything outside of the buffer is clipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::Color;
+    ///
+    /// let mut buffer = hanbun::Buffer::new(5, 2, ' ');
+    /// buffer.rectangle(0, 0, 5, 4, Color::White);
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "█▀▀▀█\n█▄▄▄█");
+    /// ```
+    pub fn rectangle(&mut self, x: i32, y: i32, width: u32, height: u32, color: Color) {
+        self.draw_rectangle(x, y, width, height, 0, EdgeColors::all(color), None);
+    }
+
+    /// Draws a rectangle `width` pixels wide and `height` pixels high
+    /// with its top-left corner at the signed (`x`, `y`) filled with `color`.
+    ///
+    /// See [`Buffer::rectangle`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::Color;
+    ///
+    /// let mut buffer = hanbun::Buffer::new(5, 2, ' ');
+    /// buffer.filled_rectangle(1, 1, 3, 2, Color::White);
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), " ▄▄▄ \n ▀▀▀ ");
+    /// ```
+    pub fn filled_rectangle(&mut self, x: i32, y: i32, width: u32, height: u32, color: Color) {
+        self.draw_rectangle(x, y, width, height, 0, EdgeColors::all(color), Some(color));
+    }
+
+    /// Draws the outline of a rectangle with corners rounded by `radius` pixels.
+    ///
+    /// See [`Buffer::rectangle`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::Color;
+    ///
+    /// let mut buffer = hanbun::Buffer::new(8, 3, ' ');
+    /// buffer.rounded_rectangle(0, 0, 8, 6, 2, Color::White);
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "▄▀▀▀▀▀▀▄\n█      █\n▀▄▄▄▄▄▄▀");
+    /// ```
+    pub fn rounded_rectangle(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        radius: u32,
+        color: Color,
+    ) {
+        self.draw_rectangle(x, y, width, height, radius, EdgeColors::all(color), None);
+    }
+
+    /// Draws a rectangle with corners rounded by `radius` pixels filled with `color`.
+    ///
+    /// See [`Buffer::rectangle`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::Color;
+    ///
+    /// let mut buffer = hanbun::Buffer::new(8, 3, ' ');
+    /// buffer.filled_rounded_rectangle(0, 0, 8, 6, 2, Color::White);
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "▄██████▄\n████████\n▀██████▀");
+    /// ```
+    pub fn filled_rounded_rectangle(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        radius: u32,
+        color: Color,
+    ) {
+        self.draw_rectangle(
+            x,
+            y,
+            width,
+            height,
+            radius,
+            EdgeColors::all(color),
+            Some(color),
+        );
+    }
+
+    /// Draws the outline of a rectangle with corners rounded by `radius` pixels
+    /// and every edge colored with its color of `edge_colors`.
+    ///
+    /// Every pixel of the outline is colored with the color of the edge it's closest to.
+    /// Use a `radius` of 0 for square corners.
+    ///
+    /// See [`Buffer::rectangle`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::{Block, Color, EdgeColors};
+    ///
+    /// let mut buffer = hanbun::Buffer::new(10, 5, ' ');
+    /// // A raised button
+    /// let edge_colors = EdgeColors {
+    ///     top: Color::White,
+    ///     left: Color::White,
+    ///     bottom: Color::DarkGrey,
+    ///     right: Color::DarkGrey,
+    /// };
+    /// buffer.edge_colored_rectangle(0, 0, 10, 10, 0, edge_colors);
+    ///
+    /// assert_eq!(buffer.get(4, 0), Some(Block::Colored(Color::White)));
+    /// assert_eq!(buffer.get(0, 4), Some(Block::Colored(Color::White)));
+    /// assert_eq!(buffer.get(4, 9), Some(Block::Colored(Color::DarkGrey)));
+    /// assert_eq!(buffer.get(9, 4), Some(Block::Colored(Color::DarkGrey)));
+    /// // The corners belong to the top and bottom edges
+    /// assert_eq!(buffer.get(9, 0), Some(Block::Colored(Color::White)));
+    /// assert_eq!(buffer.get(0, 9), Some(Block::Colored(Color::DarkGrey)));
+    /// ```
+    pub fn edge_colored_rectangle(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        radius: u32,
+        edge_colors: EdgeColors,
+    ) {
+        self.draw_rectangle(x, y, width, height, radius, edge_colors, None);
+    }
+
+    /// Draws the outline of a circle around the signed (`x`, `y`) with `color`.
+    ///
+    /// `radius` is the number of pixels from the center to the left and right of the circle.
+    /// The number of pixels to the top and bottom is chosen so that the circle looks round on the screen
+    /// in the current [`PixelMode`].
+    ///
+    /// The coordinates are the same as for [`Buffer::set`] and [`Buffer::color`]
+    /// and everything outside of the buffer is clipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::Color;
+    ///
+    /// let mut buffer = hanbun::Buffer::new(7, 4, ' ');
+    /// buffer.circle(3, 3, 3, Color::White);
+    ///
+    /// assert_eq!(
+    ///     buffer.render_to_plain_string(),
+    ///     " ▄▀▀▀▄ \n█     █\n▀▄   ▄▀\n  ▀▀▀  "
+    /// );
+    /// ```
+    pub fn circle(&mut self, x: i32, y: i32, radius: u32, color: Color) {
+        let radius_y = self.circle_radius_y(radius);
+        self.draw_ellipse(x, y, radius, radius_y, false, None, color);
+    }
+
+    /// Draws a circle around the signed (`x`, `y`) filled with `color`.
+    ///
+    /// See [`Buffer::circle`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::Color;
+    ///
+    /// let mut buffer = hanbun::Buffer::new(3, 2, ' ');
+    /// // Only the part of the circle in the buffer is drawn
+    /// buffer.filled_circle(1, -1_000_000, u32::MAX, Color::Red);
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "███\n███");
+    /// ```
+    pub fn filled_circle(&mut self, x: i32, y: i32, radius: u32, color: Color) {
+        let radius_y = self.circle_radius_y(radius);
+        self.draw_ellipse(x, y, radius, radius_y, true, None, color);
+    }
+
+    /// Draws the outline of an ellipse around the signed (`x`, `y`) with `color`.
+    ///
+    /// `radius_x` and `radius_y` are the number of pixels from the center
+    /// to the left and right and to the top and bottom of the ellipse.
+    ///
+    /// The coordinates are the same as for [`Buffer::set`] and [`Buffer::color`]
+    /// and everything outside of the buffer is clipped.
+    pub fn ellipse(&mut self, x: i32, y: i32, radius_x: u32, radius_y: u32, color: Color) {
+        self.draw_ellipse(x, y, radius_x, radius_y, false, None, color);
+    }
+
+    /// Draws an ellipse around the signed (`x`, `y`) filled with `color`.
+    ///
+    /// See [`Buffer::ellipse`].
+    pub fn filled_ellipse(&mut self, x: i32, y: i32, radius_x: u32, radius_y: u32, color: Color) {
+        self.draw_ellipse(x, y, radius_x, radius_y, true, None, color);
+    }
+
+    /// Draws the part of the outline of a circle around the signed (`x`, `y`)
+    /// from `start_angle` to `end_angle` with `color`.
+    ///
+    /// The angles are in degrees and go counterclockwise, starting at the right of the circle.
+    ///
+    /// See [`Buffer::circle`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::Color;
+    ///
+    /// let mut buffer = hanbun::Buffer::new(7, 4, ' ');
+    /// // The upper half
+    /// buffer.arc(3, 3, 3, 0.0, 180.0, Color::White);
+    ///
+    /// assert_eq!(
+    ///     buffer.render_to_plain_string(),
+    ///     " ▄▀▀▀▄ \n█     █\n       \n       "
+    /// );
+    /// ```
+    pub fn arc(
+        &mut self,
+        x: i32,
+        y: i32,
+        radius: u32,
+        start_angle: f32,
+        end_angle: f32,
+        color: Color,
+    ) {
+        let radius_y = self.circle_radius_y(radius);
+        let angles = Some((start_angle, end_angle));
+        self.draw_ellipse(x, y, radius, radius_y, false, angles, color);
+    }
+
+    /// Draws the slice of a circle around the signed (`x`, `y`)
+    /// from `start_angle` to `end_angle` filled with `color`.
+    ///
+    /// The angles are in degrees and go counterclockwise, starting at the right of the circle.
+    ///
+    /// See [`Buffer::circle`].
+    pub fn pie(
+        &mut self,
+        x: i32,
+        y: i32,
+        radius: u32,
+        start_angle: f32,
+        end_angle: f32,
+        color: Color,
+    ) {
+        let radius_y = self.circle_radius_y(radius);
+        let angles = Some((start_angle, end_angle));
+        self.draw_ellipse(x, y, radius, radius_y, true, angles, color);
+    }
+
+    /// Draws the outline of a polygon with the corners `points` with `color`.
+    ///
+    /// The last point is connected to the first point.
+    ///
+    /// The coordinates are the same as for [`Buffer::set`] and [`Buffer::color`]
+    /// and everything outside of the buffer is clipped.
+    pub fn polygon(&mut self, points: &[(i32, i32)], color: Color) {
+        for (index, &(x0, y0)) in points.iter().enumerate() {
+            let (x1, y1) = points[(index + 1) % points.len()];
+            self.line(x0, y0, x1, y1, color);
+        }
+    }
+
+    /// Draws a polygon with the corners `points` filled with `color`.
+    ///
+    /// `fill_rule` decides which parts are filled where the edges cross each other.
+    ///
+    /// See [`Buffer::polygon`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::{Block, Color, FillRule};
+    ///
+    /// let pentagram = [(10, 0), (16, 18), (0, 7), (20, 7), (4, 18)];
+    ///
+    /// let mut buffer = hanbun::Buffer::new(21, 10, ' ');
+    /// buffer.filled_polygon(&pentagram, FillRule::EvenOdd, Color::Yellow);
+    /// assert_eq!(buffer.get(10, 10), Some(Block::Empty));
+    ///
+    /// buffer.filled_polygon(&pentagram, FillRule::NonZero, Color::Yellow);
+    /// assert_eq!(buffer.get(10, 10), Some(Block::Colored(Color::Yellow)));
+    /// ```
+    pub fn filled_polygon(&mut self, points: &[(i32, i32)], fill_rule: FillRule, color: Color) {
+        self.fill_polygon(points, fill_rule, |_, _| color);
+    }
+
+    /// Draws the outline of a triangle with the corners `points` with `color`.
+    ///
+    /// See [`Buffer::polygon`].
+    pub fn triangle(&mut self, points: [(i32, i32); 3], color: Color) {
+        self.polygon(&points, color);
+    }
+
+    /// Draws a triangle with the corners `points` filled with `color`.
+    ///
+    /// See [`Buffer::polygon`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::Color;
+    ///
+    /// let mut buffer = hanbun::Buffer::new(2, 1, ' ');
+    /// // Any coordinates can be used
+    /// buffer.filled_triangle(
+    ///     [(i32::MIN, i32::MIN), (i32::MAX, i32::MIN), (i32::MAX, i32::MAX)],
+    ///     Color::Blue,
+    /// );
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "▀█");
+    /// ```
+    pub fn filled_triangle(&mut self, points: [(i32, i32); 3], color: Color) {
+        self.filled_polygon(&points, FillRule::EvenOdd, color);
+    }
+
+    /// Draws a triangle with the corners `points` filled with a gradient between `colors`,
+    /// which are the colors of the corners (Gouraud shading).
+    ///
+    /// The colors are blended as RGB colors.
+    /// [`Color::Reset`] is treated as white.
+    ///
+    /// See [`Buffer::polygon`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::{Block, Color};
+    ///
+    /// let red = Color::Rgb { r: 255, g: 0, b: 0 };
+    /// let green = Color::Rgb { r: 0, g: 255, b: 0 };
+    /// let blue = Color::Rgb { r: 0, g: 0, b: 255 };
+    ///
+    /// let mut buffer = hanbun::Buffer::new(20, 10, ' ');
+    /// buffer.gouraud_triangle([(0, 0), (18, 0), (0, 18)], [red, green, blue]);
+    ///
+    /// assert_eq!(buffer.get(0, 0), Some(Block::Colored(red)));
+    /// assert_eq!(buffer.get(18, 0), Some(Block::Colored(green)));
+    /// assert_eq!(buffer.get(0, 18), Some(Block::Colored(blue)));
+    /// // Halfway between the red and the green corner
+    /// assert_eq!(
+    ///     buffer.get(9, 0),
+    ///     Some(Block::Colored(Color::Rgb { r: 128, g: 128, b: 0 }))
+    /// );
+    ///
+    /// // Any coordinates can be used
+    /// buffer.gouraud_triangle(
+    ///     [(i32::MIN, i32::MIN), (i32::MAX, i32::MIN), (i32::MAX, i32::MAX)],
+    ///     [red, green, blue],
+    /// );
+    /// ```
+    pub fn gouraud_triangle(&mut self, points: [(i32, i32); 3], colors: [Color; 3]) {
+        let corners = points.map(|(x, y)| (x as i64, y as i64));
+        let colors = colors.map(|color| color::to_rgb(color).unwrap_or((255, 255, 255)));
+        self.fill_polygon(&points, FillRule::EvenOdd, |x, y| {
+            let weights = shape::barycentric_weights(corners, x, y);
+            let component = |component: fn((u8, u8, u8)) -> u8| {
+                let value: f32 = weights
+                    .iter()
+                    .zip(&colors)
+                    .map(|(weight, color)| weight * component(*color) as f32)
+                    .sum();
+                value.round() as u8
+            };
+            Color::Rgb {
+                r: component(|(r, _, _)| r),
+                g: component(|(_, g, _)| g),
+                b: component(|(_, _, b)| b),
+            }
+        });
+    }
+
+    /// Fills the region of pixels around (`x`, `y`) that are the same as the pixel at (`x`, `y`) with `color`,
+    /// like the bucket tool of a paint program.
+    ///
+    /// `connectivity` decides whether the region continues through diagonal neighbors.
+    /// The coordinates are the same as for [`Buffer::set`] and [`Buffer::color`],
+    /// so the region continues between the upper and lower half blocks of cells.
+    /// Cells with printed text are never part of a region, so they are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::{Block, Color, Connectivity};
+    ///
+    /// let mut buffer = hanbun::Buffer::new(6, 3, ' ');
+    /// buffer.rectangle(0, 0, 6, 5, Color::White);
+    /// buffer.flood_fill(2, 2, Connectivity::Four, Color::Blue);
+    ///
+    /// assert_eq!(buffer.get(1, 1), Some(Block::Colored(Color::Blue)));
+    /// assert_eq!(buffer.get(4, 3), Some(Block::Colored(Color::Blue)));
+    /// assert_eq!(buffer.get(0, 5), Some(Block::Empty));
+    ///
+    /// let mut buffer = hanbun::Buffer::new(6, 1, '.');
+    /// buffer.print(0, 0, "日b");
+    /// buffer.flood_fill(4, 0, Connectivity::Four, Color::Blue);
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "日b███");
+    ///
+    /// // Printed spaces are kept too
+    /// let mut buffer = hanbun::Buffer::new(5, 3, ' ');
+    /// buffer.print(1, 2, "a b");
+    /// buffer.flood_fill(0, 0, Connectivity::Four, Color::Red);
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "█████\n█a b█\n█████");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if (`x`, `y`) is out of the buffer's range.
+    pub fn flood_fill(&mut self, x: usize, y: usize, connectivity: Connectivity, color: Color) {
+        let region_block = self
+            .get(x, y)
+            .unwrap_or_else(|| panic!("filling at ({}, {}) (out of range)", x, y));
+        let block = Block::Colored(color);
+        if region_block == block {
+            return;
+        }
+
+        let (pixel_width, pixel_height) = self.pixel_size();
+        let mut pixels = vec![(x, y)];
+        while let Some((x, y)) = pixels.pop() {
+            if self.get(x, y) != Some(region_block) || self.has_text(x, y) {
+                continue;
+            }
+            self.put(x, y, block, "filling");
+
+            for (offset_x, offset_y) in connectivity.offsets() {
+                let (neighbor_x, neighbor_y) = (x as i64 + offset_x, y as i64 + offset_y);
+                let is_in_range = (0..pixel_width as i64).contains(&neighbor_x)
+                    && (0..pixel_height as i64).contains(&neighbor_y);
+                if is_in_range {
+                    pixels.push((neighbor_x as usize, neighbor_y as usize));
+                }
+            }
+        }
+    }
+
+    /// Prints `string` to (`x`, `y`).
+    ///
+    /// Wide characters like `日` occupy two cells
+    /// and combining characters are printed together with the character they belong to.
+    /// If `string` doesn't fit into the rest of the row, it continues on the next row.
+    /// Use [`Buffer::print_clipped`] to cut it off instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut buffer = hanbun::Buffer::new(7, 1, '.');
+    /// buffer.print(0, 0, "日本e\u{301}!");
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "日本e\u{301}!.");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if (`x`, `y`) is out of the buffer's range.
+    pub fn print(&mut self, x: usize, y: usize, string: &str) {
+        self.print_cells(x, y, string, None, None, Attributes::default());
+    }
+
+    /// Prints a colored `string` to (`x`, `y`) with `color`.
+    ///
+    /// # Panics
     ///
     /// Panics if (`x`, `y`) is out of the buffer's range.
     pub fn colored_print(&mut self, x: usize, y: usize, string: &str, color: Color) {
-        let position = x + self.width * (y / 2);
+        self.print_cells(x, y, string, Some(color), None, Attributes::default());
+    }
+
+    /// Prints `string` to (`x`, `y`), cutting off everything that doesn't fit into the row.
+    ///
+    /// Returns `true` if anything was cut off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut buffer = hanbun::Buffer::new(5, 2, '.');
+    ///
+    /// assert!(buffer.print_clipped(2, 0, "Hello"));
+    /// assert!(buffer.print_clipped(0, 8, "Hello"));
+    /// assert!(!buffer.print_clipped(0, 2, "Hi"));
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "..Hel\nHi...");
+    /// ```
+    pub fn print_clipped(&mut self, x: usize, y: usize, string: &str) -> bool {
+        let (string, is_clipped) = self.clip(x, y, string);
+        self.print(x, y, string);
+        is_clipped
+    }
 
-        for (index, char) in string.chars().enumerate() {
-            let cell = self
-                .cells
-                .get_mut(index + position)
-                .unwrap_or_else(|| panic!("printing at ({}, {}) (out of range)", x, y));
+    /// Prints a colored `string` to (`x`, `y`) with `color`,
+    /// cutting off everything that doesn't fit into the row.
+    ///
+    /// Returns `true` if anything was cut off.
+    pub fn colored_print_clipped(
+        &mut self,
+        x: usize,
+        y: usize,
+        string: &str,
+        color: Color,
+    ) -> bool {
+        let (string, is_clipped) = self.clip(x, y, string);
+        self.colored_print(x, y, string, color);
+        is_clipped
+    }
 
+    /// Prints `string` to the signed (`x`, `y`), cutting off everything that is outside of the buffer.
+    ///
+    /// Returns `true` if anything was cut off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut buffer = hanbun::Buffer::new(5, 1, '.');
+    ///
+    /// assert!(buffer.print_at(-3, 0, "Hello"));
+    /// assert!(buffer.print_at(0, -2, "Hello"));
+    ///
+    /// assert_eq!(buffer.render_to_plain_string(), "lo...");
+    /// ```
+    pub fn print_at(&mut self, x: i32, y: i32, string: &str) -> bool {
+        let (x, string, is_clipped) = clip_left(x, string);
+        if let Ok(y) = usize::try_from(y) {
+            self.print_clipped(x, y, string) || is_clipped
+        } else {
+            is_clipped || string.width() != 0
+        }
+    }
+
+    /// Prints a colored `string` to the signed (`x`, `y`) with `color`,
+    /// cutting off everything that is outside of the buffer.
+    ///
+    /// Returns `true` if anything was cut off.
+    pub fn colored_print_at(&mut self, x: i32, y: i32, string: &str, color: Color) -> bool {
+        let (x, string, is_clipped) = clip_left(x, string);
+        if let Ok(y) = usize::try_from(y) {
+            self.colored_print_clipped(x, y, string, color) || is_clipped
+        } else {
+            is_clipped || string.width() != 0
+        }
+    }
+
+    /// Prints `string` to (`x`, `y`) on a background colored with `background`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if (`x`, `y`) is out of the buffer's range.
+    pub fn background_print(&mut self, x: usize, y: usize, string: &str, background: Color) {
+        self.print_cells(x, y, string, None, Some(background), Attributes::default());
+    }
+
+    /// Prints a colored `string` to (`x`, `y`) with `color` on a background colored with `background`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::{Color, ColorDepth};
+    ///
+    /// let mut buffer = hanbun::Buffer::with_writer(6, 1, ' ', Vec::new());
+    /// buffer.set_color_depth(ColorDepth::Ansi16);
+    /// // A highlighted button
+    /// buffer.colored_background_print(1, 0, "OK", Color::Black, Color::White);
+    ///
+    /// assert_eq!(
+    ///     buffer.render_to_string(),
+    ///     " \x1b[38;5;0m\x1b[48;5;15mOK\x1b[49m   \x1b[0m"
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if (`x`, `y`) is out of the buffer's range.
+    pub fn colored_background_print(
+        &mut self,
+        x: usize,
+        y: usize,
+        string: &str,
+        color: Color,
+        background: Color,
+    ) {
+        self.print_cells(
+            x,
+            y,
+            string,
+            Some(color),
+            Some(background),
+            Attributes::default(),
+        );
+    }
+
+    /// Prints `string` to (`x`, `y`) with `attributes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::{Attribute, Attributes, ColorDepth};
+    ///
+    /// let mut buffer = hanbun::Buffer::with_writer(6, 1, ' ', Vec::new());
+    /// buffer.set_color_depth(ColorDepth::Ansi16);
+    /// buffer.styled_print(0, 0, "Hi", Attributes::from(Attribute::Bold));
+    ///
+    /// // The attributes are reset after the text
+    /// assert_eq!(buffer.render_to_string(), "\x1b[1mHi\x1b[0m    ");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if (`x`, `y`) is out of the buffer's range.
+    pub fn styled_print(&mut self, x: usize, y: usize, string: &str, attributes: Attributes) {
+        self.print_cells(x, y, string, None, None, attributes);
+    }
+
+    /// Prints `string` to (`x`, `y`) with `attributes`, colored with `color`
+    /// and on a background colored with `background`, if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if (`x`, `y`) is out of the buffer's range.
+    pub fn colored_styled_print(
+        &mut self,
+        x: usize,
+        y: usize,
+        string: &str,
+        color: Option<Color>,
+        background: Option<Color>,
+        attributes: Attributes,
+    ) {
+        self.print_cells(x, y, string, color, background, attributes);
+    }
+
+    /// Prints `string` to (`x`, `y`) on top of the half blocks that are already there.
+    ///
+    /// The background of every character is the color of the half blocks below it,
+    /// or their average color if they have different colors.
+    /// This keeps text readable on top of pictures.
+    /// The half blocks are kept and show up again once a half block of the cell is set or colored.
+    ///
+    /// Wide characters like `日` cover the cell to their right too.
+    /// The half blocks of that cell are not kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hanbun::{Block, Color, ColorDepth};
+    ///
+    /// let mut buffer = hanbun::Buffer::with_writer(3, 1, ' ', Vec::new());
+    /// buffer.set_color_depth(ColorDepth::TrueColor);
+    /// buffer.color(0, 0, Color::Rgb { r: 255, g: 0, b: 0 });
+    /// buffer.color(0, 1, Color::Rgb { r: 0, g: 0, b: 255 });
+    /// buffer.set(1, 0);
+    /// buffer.overlay_print(0, 0, "ab");
+    ///
+    /// // "a" is on the average of red and blue, "b" on the uncolored half block has no background
+    /// assert_eq!(
+    ///     buffer.render_to_string(),
+    ///     "\x1b[48;2;127;0;127ma\x1b[0mb "
+    /// );
+    ///
+    /// // The half blocks below "a" show up again, but "日" covered the ones of the cell to its right
+    /// buffer.overlay_print(0, 0, "日");
+    /// buffer.color(0, 0, Color::Rgb { r: 255, g: 0, b: 0 });
+    /// assert_eq!(buffer.get(0, 1), Some(Block::Colored(Color::Rgb { r: 0, g: 0, b: 255 })));
+    /// assert_eq!(buffer.get(1, 0), Some(Block::Empty));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if (`x`, `y`) is out of the buffer's range.
+    pub fn overlay_print(&mut self, x: usize, y: usize, string: &str) {
+        self.print_with(x, y, string, |cell, char, combining| {
+            overlay(cell, char, combining, None)
+        });
+    }
+
+    /// Prints a colored `string` to (`x`, `y`) with `color` on top of the half blocks that are already there.
+    ///
+    /// See [`Buffer::overlay_print`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if (`x`, `y`) is out of the buffer's range.
+    pub fn colored_overlay_print(&mut self, x: usize, y: usize, string: &str, color: Color) {
+        self.print_with(x, y, string, |cell, char, combining| {
+            overlay(cell, char, combining, Some(color))
+        });
+    }
+
+    fn print_cells(
+        &mut self,
+        x: usize,
+        y: usize,
+        string: &str,
+        char_color: Option<Color>,
+        char_background: Option<Color>,
+        char_attributes: Attributes,
+    ) {
+        self.print_with(x, y, string, |cell, char, combining| {
             *cell = Cell {
-                upper_block: None,
-                lower_block: None,
+                upper_block: Block::Empty,
+                lower_block: Block::Empty,
                 char: Some(char),
-                char_color: Some(color),
-            };
+                combining,
+                char_color,
+                char_background,
+                char_attributes,
+                printed: true,
+                continuation: false,
+                mosaic: None,
+            }
+        });
+    }
+
+    /// Sets the pixel at (`x`, `y`) to `block`. `action` describes this for the panic message.
+    ///
+    /// Setting a pixel removes the character of the cell.
+    /// Unsetting the last pixel of a cell makes it fall back to its character or the cleared cell.
+    /// Unsetting a pixel of a cell without half blocks does nothing.
+    fn put(&mut self, x: usize, y: usize, block: Block, action: &str) {
+        let (columns, rows) = self.pixel_mode.size();
+        let position = x / columns + self.width * (y / rows);
+        if !block.is_empty() {
+            self.split_wide_char(position);
+        }
+        let pixel_mode = self.pixel_mode;
+        let cleared_cell = &self.cleared_cell;
+        let cell = self
+            .cells
+            .get_mut(position)
+            .unwrap_or_else(|| panic!("{} block at ({}, {}) (out of range)", action, x, y));
+
+        if block.is_empty() && cell.upper_block.is_empty() && cell.lower_block.is_empty() {
+            return;
+        }
+
+        cell.put(pixel_mode, x % columns, y % rows, block);
+        if block.is_empty() {
+            if cell.is_blank() {
+                *cell = cleared_cell.clone();
+            }
+        } else {
+            cell.char = None;
+            cell.combining = None;
+            cell.char_color = None;
+            cell.char_background = None;
+            cell.char_attributes = Attributes::default();
+            cell.printed = false;
+        }
+    }
+
+    /// Draws a rectangle with its edges colored with `edge_colors` and filled with `fill`, if any.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_rectangle(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        radius: u32,
+        edge_colors: EdgeColors,
+        fill: Option<Color>,
+    ) {
+        let (x, y, width, height) = (x as i64, y as i64, width as i64, height as i64);
+        let radius = (radius as i64).min(width / 2).min(height / 2);
+        let is_inside = |column: i64, row: i64| {
+            if column < 0 || row < 0 || column >= width || row >= height {
+                return false;
+            }
+            let inset = shape::corner_inset(row, height, radius);
+            column >= inset && column < width - inset
+        };
+
+        // Only the pixels that are in the buffer's range are visited
+        let (pixel_width, pixel_height) = self.pixel_size();
+        for pixel_y in y.max(0)..(y + height).min(pixel_height as i64) {
+            for pixel_x in x.max(0)..(x + width).min(pixel_width as i64) {
+                let (column, row) = (pixel_x - x, pixel_y - y);
+                if !is_inside(column, row) {
+                    continue;
+                }
+
+                let is_edge = !is_inside(column - 1, row)
+                    || !is_inside(column + 1, row)
+                    || !is_inside(column, row - 1)
+                    || !is_inside(column, row + 1);
+                let color = if is_edge {
+                    edge_colors.at(column, row, width, height)
+                } else if let Some(fill) = fill {
+                    fill
+                } else {
+                    continue;
+                };
+                self.put(
+                    pixel_x as usize,
+                    pixel_y as usize,
+                    Block::Colored(color),
+                    "drawing",
+                );
+            }
+        }
+    }
+
+    /// Returns the vertical radius of a circle with the horizontal `radius` that makes it look round.
+    fn circle_radius_y(&self, radius: u32) -> u32 {
+        // Cells are about twice as high as they are wide
+        let (columns, rows) = self.pixel_mode.size();
+        let pixel_aspect_ratio = 2.0 * columns as f32 / rows as f32;
+        (radius as f32 / pixel_aspect_ratio).round() as u32
+    }
+
+    /// Draws an ellipse with `color` that is filled if `filled` is `true`.
+    ///
+    /// If `angles` is set, only the part from the start angle to the end angle is drawn.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_ellipse(
+        &mut self,
+        x: i32,
+        y: i32,
+        radius_x: u32,
+        radius_y: u32,
+        filled: bool,
+        angles: Option<(f32, f32)>,
+        color: Color,
+    ) {
+        let max_radius = i32::MAX as u32;
+        let (radius_x, radius_y) = (
+            radius_x.min(max_radius) as i64,
+            radius_y.min(max_radius) as i64,
+        );
+        let (x, y) = (x as i64, y as i64);
+        let (columns, rows) = self.pixel_mode.size();
+        let pixel_aspect_ratio = 2.0 * columns as f32 / rows as f32;
+        let (pixel_width, pixel_height) = self.pixel_size();
+        let (pixel_width, pixel_height) = (pixel_width as i64, pixel_height as i64);
+        // Draws the pixels from `first_offset_x` to `last_offset_x` in the row `offset_y`
+        let mut plot_span = |offset_y: i64, first_offset_x: i64, last_offset_x: i64| {
+            // Only the pixels that are in the buffer's range are visited
+            for offset_x in first_offset_x.max(-x)..=last_offset_x.min(pixel_width - 1 - x) {
+                if let Some((start_angle, end_angle)) = angles {
+                    let is_center = offset_x == 0 && offset_y == 0;
+                    // The y axis points down
+                    let angle = (-offset_y as f32 * pixel_aspect_ratio)
+                        .atan2(offset_x as f32)
+                        .to_degrees();
+                    if !is_center && !shape::is_between(angle, start_angle, end_angle) {
+                        continue;
+                    }
+                }
+                self.plot(x + offset_x, y + offset_y, Block::Colored(color));
+            }
+        };
+
+        for offset_y in (-radius_y).max(-y)..=radius_y.min(pixel_height - 1 - y) {
+            let row = offset_y.abs();
+            let half_width = shape::ellipse_half_width(radius_x, radius_y, row);
+            if filled {
+                plot_span(offset_y, -half_width, half_width);
+            } else {
+                // The outline continues where the row further from the center ends
+                let outer_half_width = if row == radius_y {
+                    -1
+                } else {
+                    shape::ellipse_half_width(radius_x, radius_y, row + 1)
+                };
+                let inner_half_width = (outer_half_width + 1).min(half_width);
+                plot_span(offset_y, -half_width, -inner_half_width);
+                plot_span(offset_y, inner_half_width, half_width);
+            }
+        }
+    }
+
+    /// Draws a polygon with the corners `points` filled according to `fill_rule`,
+    /// coloring every pixel with the color `color_at` returns for it.
+    ///
+    /// The edges are drawn too so that the filled polygon covers its outline.
+    fn fill_polygon(
+        &mut self,
+        points: &[(i32, i32)],
+        fill_rule: FillRule,
+        color_at: impl Fn(i64, i64) -> Color,
+    ) {
+        if points.is_empty() {
+            return;
+        }
+
+        let corners: Vec<(i64, i64)> = points.iter().map(|&(x, y)| (x as i64, y as i64)).collect();
+        let (pixel_width, pixel_height) = self.pixel_size();
+        let top = corners.iter().map(|(_, y)| *y).min().unwrap().max(0);
+        let bottom = corners
+            .iter()
+            .map(|(_, y)| *y)
+            .max()
+            .unwrap()
+            .min(pixel_height as i64 - 1);
+        for row in top..=bottom {
+            shape::polygon_spans(&corners, row, fill_rule, |first_column, last_column| {
+                // Only the pixels that are in the buffer's range are visited
+                for column in first_column.max(0)..=last_column.min(pixel_width as i64 - 1) {
+                    let color = color_at(column, row);
+                    self.put(
+                        column as usize,
+                        row as usize,
+                        Block::Colored(color),
+                        "drawing",
+                    );
+                }
+            });
+        }
+
+        for (index, &(x0, y0)) in points.iter().enumerate() {
+            let (x1, y1) = points[(index + 1) % points.len()];
+            let (x0, y0, x1, y1) = (x0 as i64, y0 as i64, x1 as i64, y1 as i64);
+            shape::line(x0, y0, x1, y1, self.pixel_bounds(), |x, y| {
+                let color = color_at(x, y);
+                self.plot(x, y, Block::Colored(color));
+            });
+        }
+    }
+
+    /// Sets the pixel at the signed (`x`, `y`) to `block` if it's in the buffer's range.
+    fn plot(&mut self, x: i64, y: i64, block: Block) {
+        if let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) {
+            if self.contains_pixel(x, y) {
+                self.put(x, y, block, "drawing");
+            }
+        }
+    }
+
+    /// Returns `true` if the cell of the pixel at (`x`, `y`) shows printed text
+    /// rather than the character it was cleared with.
+    fn has_text(&self, x: usize, y: usize) -> bool {
+        let (columns, rows) = self.pixel_mode.size();
+        self.cell(x / columns, y / rows).is_some_and(|cell| {
+            // The cells after wide characters have no character of their own
+            cell.is_continuation() || cell.printed
+        })
+    }
+
+    /// Returns `true` if the cell printed to at (`x`, `y`) is in the buffer's range.
+    fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y / 2 < self.height
+    }
+
+    /// Returns `true` if the pixel at (`x`, `y`) is in the buffer's range.
+    fn contains_pixel(&self, x: usize, y: usize) -> bool {
+        let (columns, rows) = self.pixel_mode.size();
+        x / columns < self.width && y / rows < self.height
+    }
+
+    /// Converts the signed (`x`, `y`) to unsigned coordinates if the half block is in the buffer's range.
+    fn unsigned(&self, x: i32, y: i32) -> Option<(usize, usize)> {
+        let x = usize::try_from(x).ok()?;
+        let y = usize::try_from(y).ok()?;
+        Some((x, y)).filter(|&(x, y)| self.contains_pixel(x, y))
+    }
+
+    /// Returns the part of `string` that fits into the row when printed to (`x`, `y`)
+    /// and whether anything was cut off.
+    fn clip<'a>(&self, x: usize, y: usize, string: &'a str) -> (&'a str, bool) {
+        if !self.contains(x, y) {
+            return ("", string.width() != 0);
+        }
+
+        let mut columns = self.width - x;
+        for (index, grapheme) in string.grapheme_indices(true) {
+            let width = grapheme.width().min(2);
+            if width > columns {
+                return (&string[..index], true);
+            }
+            columns -= width;
+        }
+        (string, false)
+    }
+
+    /// Calls `print` with every grapheme of `string`, split into its first character and the rest,
+    /// and the cell it's printed to.
+    ///
+    /// Graphemes that are two columns wide are followed by a continuation cell
+    /// and graphemes that have no width are skipped.
+    fn print_with(
+        &mut self,
+        x: usize,
+        y: usize,
+        string: &str,
+        print: impl Fn(&mut Cell, char, Option<String>),
+    ) {
+        let mut position = x + self.width * (y / 2);
+
+        for grapheme in string.graphemes(true) {
+            let width = grapheme.width().min(2);
+            if width == 0 {
+                continue;
+            }
+
+            if width == 2 && position % self.width == self.width - 1 {
+                // The wide character doesn't fit into the rest of the row
+                print(self.printed_cell(position, x, y), ' ', None);
+                position += 1;
+            }
+
+            let mut chars = grapheme.chars();
+            let char = chars.next().unwrap();
+            let combining = Some(chars.as_str())
+                .filter(|rest| !rest.is_empty())
+                .map(String::from);
+            print(self.printed_cell(position, x, y), char, combining);
+            position += 1;
+
+            if width == 2 {
+                *self.printed_cell(position, x, y) = Cell {
+                    upper_block: Block::Empty,
+                    lower_block: Block::Empty,
+                    char: None,
+                    combining: None,
+                    char_color: None,
+                    char_background: None,
+                    char_attributes: Attributes::default(),
+                    printed: true,
+                    continuation: true,
+                    mosaic: None,
+                };
+                position += 1;
+            }
+        }
+    }
+
+    /// Returns the cell at `position` to print to.
+    fn printed_cell(&mut self, position: usize, x: usize, y: usize) -> &mut Cell {
+        self.split_wide_char(position);
+        self.cells
+            .get_mut(position)
+            .unwrap_or_else(|| panic!("printing at ({}, {}) (out of range)", x, y))
+    }
+
+    /// Replaces the wide character that covers the cell at `position` with spaces, if there is one,
+    /// so that the cell can be overwritten without leaving half of a wide character behind.
+    fn split_wide_char(&mut self, position: usize) {
+        if position >= self.cells.len() {
+            return;
+        }
+
+        let column = position % self.width;
+        if column != 0 && self.cells[position].is_continuation() {
+            let continuation_cell = &mut self.cells[position];
+            continuation_cell.char = Some(' ');
+            continuation_cell.continuation = false;
+            let wide_cell = &mut self.cells[position - 1];
+            wide_cell.char = Some(' ');
+            wide_cell.combining = None;
+        } else if column + 1 != self.width {
+            if let Some(next_cell) = self.cells.get_mut(position + 1) {
+                if next_cell.is_continuation() {
+                    next_cell.char = Some(' ');
+                    next_cell.continuation = false;
+                    let wide_cell = &mut self.cells[position];
+                    wide_cell.char = Some(' ');
+                    wide_cell.combining = None;
+                }
+            }
+        }
+    }
+}
+
+/// Returns a cell without half blocks that shows `char` colored with `char_color`.
+fn cleared_cell(char: char, char_color: Option<Color>) -> Cell {
+    Cell {
+        upper_block: Block::Empty,
+        lower_block: Block::Empty,
+        char: Some(char),
+        combining: None,
+        char_color,
+        char_background: None,
+        char_attributes: Attributes::default(),
+        printed: false,
+        continuation: false,
+        mosaic: None,
+    }
+}
+
+/// Returns the column `string` starts at when printed to the signed column `x`,
+/// the part of `string` that is right of the buffer's left edge and whether anything was cut off.
+///
+/// A wide character that is cut in half is cut off entirely.
+fn clip_left(x: i32, string: &str) -> (usize, &str, bool) {
+    if let Ok(x) = usize::try_from(x) {
+        return (x, string, false);
+    }
+
+    let mut x = x as i64;
+    for (index, grapheme) in string.grapheme_indices(true) {
+        if x >= 0 {
+            return (x as usize, &string[index..], true);
+        }
+        x += grapheme.width().min(2) as i64;
+    }
+    (0, "", string.width() != 0)
+}
+
+/// Puts `char` on top of the half blocks of `cell`.
+fn overlay(cell: &mut Cell, char: char, combining: Option<String>, char_color: Option<Color>) {
+    cell.char = Some(char);
+    cell.combining = combining;
+    cell.char_color = char_color;
+    cell.char_background = None;
+    cell.char_attributes = Attributes::default();
+    cell.printed = true;
+}
+
+/// Returns the terminal column or row that is `offset` cells after `start`.
+///
+/// Positions that don't fit into escape sequences are clamped to the largest one that does.
+fn terminal_offset(start: u16, offset: usize) -> u16 {
+    // Escape sequences count from 1, which crossterm adds to the position
+    start
+        .saturating_add(u16::try_from(offset).unwrap_or(u16::MAX))
+        .min(u16::MAX - 1)
+}
+
+/// Writes all `cells` in rows of `width` cells at `origin`.
+fn draw_all(
+    writer: &mut impl Write,
+    cells: &[Cell],
+    width: usize,
+    origin: Origin,
+    style: &mut TerminalStyle,
+) -> crossterm::Result<()> {
+    if width == 0 {
+        return Ok(());
+    }
+
+    for (y, cells) in cells.chunks(width).enumerate() {
+        match origin {
+            Origin::Cursor => {
+                if y != 0 {
+                    // Otherwise the background color might fill the new row if the terminal scrolls
+                    style.reset(writer)?;
+                    writer.write_all(b"\n")?;
+                }
+            }
+            Origin::Position { column, row } => {
+                queue!(
+                    writer,
+                    MoveTo(terminal_offset(column, 0), terminal_offset(row, y))
+                )?;
+            }
+        }
+
+        for cell in cells {
+            draw_cell(writer, cell, style)?;
+        }
+    }
+    style.reset(writer)
+}
+
+/// Returns the character that represents `cell` without any colors.
+fn plain_char(cell: &Cell, legacy_symbols: bool) -> char {
+    if let Some(char) = cell.char {
+        return char;
+    }
+    if let Some(mosaic) = drawn_mosaic(cell, legacy_symbols) {
+        return mosaic.plain_char();
+    }
+
+    match (cell.upper_block.is_empty(), cell.lower_block.is_empty()) {
+        (false, false) => '█',
+        (false, true) => '▀',
+        (true, false) => '▄',
+        (true, true) => ' ',
+    }
+}
+
+/// Returns the pixels of `cell` that are drawn instead of its half blocks, if any.
+///
+/// Pixels that need the Symbols for Legacy Computing are drawn as half blocks if `legacy_symbols` is `false`.
+fn drawn_mosaic(cell: &Cell, legacy_symbols: bool) -> Option<Mosaic> {
+    cell.mosaic
+        .filter(|mosaic| legacy_symbols || !mosaic.mode.needs_legacy_symbols())
+}
+
+/// Returns a shade character that represents how bright `cell` is.
+///
+/// Cells without half blocks are returned as they are.
+fn shade_char(cell: &Cell, legacy_symbols: bool) -> char {
+    if cell.char.is_some() || cell.upper_block.is_empty() && cell.lower_block.is_empty() {
+        return plain_char(cell, legacy_symbols);
+    }
+
+    let brightness = |block| match block {
+        Block::Empty => 0.0,
+        Block::Uncolored => 1.0,
+        Block::Colored(color) => color::luminance(color),
+    };
+    let brightness = (brightness(cell.upper_block) + brightness(cell.lower_block)) / 2.0;
+    [' ', '░', '▒', '▓', '█'][(brightness * 4.0).round() as usize]
+}
+
+/// Returns the character that represents `cell` and the foreground and background colors it's drawn with.
+///
+/// [`None`] stands for the terminal's default color.
+///
+/// See [`Block`] for a table of all combinations.
+fn appearance(cell: &Cell, legacy_symbols: bool) -> (char, Option<Color>, Option<Color>) {
+    use Block::{Colored, Empty, Uncolored};
+
+    if let Some(char) = cell.char {
+        let background =
+            cell.char_background
+                .or_else(|| match (cell.upper_block, cell.lower_block) {
+                    (Colored(upper_color), Colored(lower_color)) => {
+                        Some(color::blend(upper_color, lower_color))
+                    }
+                    (Colored(color), _) | (_, Colored(color)) => Some(color),
+                    _ => None,
+                });
+        return (char, cell.char_color, background);
+    }
+    if let Some(mosaic) = drawn_mosaic(cell, legacy_symbols) {
+        return mosaic.appearance();
+    }
+
+    match (cell.upper_block, cell.lower_block) {
+        (Empty, Empty) => (' ', None, None),
+        (Empty, Uncolored) => ('▄', None, None),
+        (Empty, Colored(lower_color)) => ('▄', Some(lower_color), None),
+        (Uncolored, Empty) => ('▀', None, None),
+        (Uncolored, Uncolored) => ('█', None, None),
+        (Uncolored, Colored(lower_color)) => ('▀', None, Some(lower_color)),
+        (Colored(upper_color), Empty) => ('▀', Some(upper_color), None),
+        (Colored(upper_color), Uncolored) => ('▄', None, Some(upper_color)),
+        (Colored(upper_color), Colored(lower_color)) => {
+            if upper_color == lower_color {
+                ('█', Some(upper_color), None)
+            } else {
+                ('▀', Some(upper_color), Some(lower_color))
+            }
+        }
+    }
+}
+
+/// The colors and attributes the terminal is currently drawing with.
+///
+/// This is used to only write changes when they actually change something.
+/// [`None`] stands for the terminal's default color.
+struct TerminalStyle {
+    foreground: Option<Color>,
+    background: Option<Color>,
+    attributes: Attributes,
+    color_depth: ColorDepth,
+    quantization: Quantization,
+    monochrome_style: MonochromeStyle,
+    legacy_symbols: bool,
+}
+
+impl TerminalStyle {
+    fn new(
+        color_depth: ColorDepth,
+        quantization: Quantization,
+        monochrome_style: MonochromeStyle,
+        legacy_symbols: bool,
+    ) -> TerminalStyle {
+        TerminalStyle {
+            foreground: None,
+            background: None,
+            attributes: Attributes::default(),
+            color_depth,
+            quantization,
+            monochrome_style,
+            legacy_symbols,
+        }
+    }
+
+    /// Sets the colors and attributes, mapping the colors to ones the terminal can display first.
+    fn set(
+        &mut self,
+        writer: &mut impl Write,
+        foreground: Option<Color>,
+        background: Option<Color>,
+        attributes: Attributes,
+    ) -> crossterm::Result<()> {
+        let downsample = |color| color::downsample(color, self.color_depth, self.quantization);
+        let foreground = foreground.map(downsample);
+        let background = background.map(downsample);
+        let is_colored = self.foreground.is_some() || self.background.is_some();
+        let resets_colors = is_colored && foreground.is_none() && background.is_none();
+        if attributes != self.attributes || resets_colors {
+            // This resets the attributes too, which is the only way
+            // to turn off single attributes that works everywhere
+            if is_colored || !self.attributes.is_empty() {
+                queue!(writer, ResetColor)?;
+            }
+            self.foreground = None;
+            self.background = None;
+            if !attributes.is_empty() {
+                queue!(writer, SetAttributes(attributes))?;
+            }
+            self.attributes = attributes;
+        }
+        if foreground != self.foreground {
+            queue!(
+                writer,
+                SetForegroundColor(foreground.unwrap_or(Color::Reset))
+            )?;
+        }
+        if background != self.background {
+            queue!(
+                writer,
+                SetBackgroundColor(background.unwrap_or(Color::Reset))
+            )?;
+        }
+        self.foreground = foreground;
+        self.background = background;
+        Ok(())
+    }
+
+    fn reset(&mut self, writer: &mut impl Write) -> crossterm::Result<()> {
+        self.set(writer, None, None, Attributes::default())
+    }
+}
+
+/// Writes a single cell at the current cursor position.
+fn draw_cell(
+    writer: &mut impl Write,
+    cell: &Cell,
+    style: &mut TerminalStyle,
+) -> crossterm::Result<()> {
+    if cell.is_continuation() {
+        return Ok(());
+    }
+
+    if style.color_depth == ColorDepth::Monochrome {
+        let char = match style.monochrome_style {
+            MonochromeStyle::Blocks => plain_char(cell, style.legacy_symbols),
+            MonochromeStyle::Shades => shade_char(cell, style.legacy_symbols),
+        };
+        write!(writer, "{}", char)?;
+    } else {
+        let (char, mut foreground, background) = appearance(cell, style.legacy_symbols);
+        let attributes = if cell.char.is_some() {
+            cell.char_attributes
+        } else {
+            Attributes::default()
+        };
+        if char == ' ' && attributes.is_empty() {
+            // The foreground color isn't visible so we keep whatever is set
+            foreground = style.foreground;
+        }
+        style.set(writer, foreground, background, attributes)?;
+        write!(writer, "{}", char)?;
+    }
+
+    if cell.char.is_some() {
+        if let Some(combining) = &cell.combining {
+            writer.write_all(combining.as_bytes())?;
         }
     }
+    Ok(())
 }
@@ -0,0 +1,414 @@
+use crate::{color, Block, Color, Quantization};
+
+/// How [`Buffer::set`](crate::Buffer::set), [`Buffer::color`](crate::Buffer::color) and friends
+/// divide a cell into pixels.
+///
+/// The modes can be mixed in a buffer: every cell is drawn in the finest mode it was drawn to with,
+/// so drawing coarser pixels into it sets all the finer pixels they cover instead.
+///
+/// # Examples
+///
+/// ```
+/// use hanbun::PixelMode;
+///
+/// // A plot of a sine wave using braille dots
+/// let mut buffer = hanbun::Buffer::new(8, 2, ' ');
+/// buffer.set_pixel_mode(PixelMode::Braille);
+/// for x in 0..16 {
+///     let y = 3.5 - (x as f32 / 16.0 * std::f32::consts::TAU).sin() * 3.5;
+///     buffer.set(x, y.round() as usize);
+/// }
+///
+/// assert_eq!(buffer.render_to_plain_string(), "⠠⠊⠉⠢    \n⠁   ⠑⢄⣀⠔");
+/// ```
+///
+/// Mixing modes:
+///
+/// ```
+/// use hanbun::PixelMode;
+///
+/// let mut buffer = hanbun::Buffer::new(2, 1, ' ');
+/// buffer.set_pixel_mode(PixelMode::Braille);
+/// buffer.set(0, 0);
+/// buffer.set_pixel_mode(PixelMode::HalfBlocks);
+/// buffer.set(0, 1);
+/// buffer.set(1, 1);
+///
+/// // The first cell stays braille and the lower half block becomes the lower 4 dots
+/// assert_eq!(buffer.render_to_plain_string(), "⣥▄");
+/// assert_eq!(buffer.cell(0, 0).unwrap().mosaic.unwrap().mode, PixelMode::Braille);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelMode {
+    /// Every cell is split into an upper and a lower half block (`▀` and `▄`).
+    ///
+    /// This is the default.
+    HalfBlocks,
+    /// Every cell is split into 2×2 quadrants (`▘`, `▝`, `▖`, `▗` and combinations of them).
+    ///
+    /// Every cell can only show two colors, so the quadrants of a cell are drawn in the two colors
+    /// that are most common among them.
+    Quadrants,
+    /// Every cell is split into 2×4 braille dots (`⠁`, `⠂`, `⠄`, `⡀`, `⠈`, `⠐`, `⠠`, `⢀` and combinations of them).
+    ///
+    /// Dots are either on or off and every cell can only show one color,
+    /// so the dots of a cell are drawn in the color that is most common among them.
+    /// Braille is well suited for plots and line drawings.
+    Braille,
+    /// Every cell is split into 2×3 sextants from the Symbols for Legacy Computing (Unicode 13).
+    ///
+    /// Like with [`PixelMode::Quadrants`], the sextants of a cell are drawn in two colors.
+    /// Not every font has these characters.
+    /// See [`Buffer::set_legacy_symbols`](crate::Buffer::set_legacy_symbols).
+    Sextants,
+    /// Every cell is split into 2×4 octants from the Symbols for Legacy Computing Supplement (Unicode 16).
+    ///
+    /// Like with [`PixelMode::Quadrants`], the octants of a cell are drawn in two colors.
+    /// Not every font has these characters.
+    /// See [`Buffer::set_legacy_symbols`](crate::Buffer::set_legacy_symbols).
+    Octants,
+}
+
+impl PixelMode {
+    /// Returns how many pixels a cell has horizontally and vertically.
+    pub fn size(self) -> (usize, usize) {
+        match self {
+            PixelMode::HalfBlocks => (1, 2),
+            PixelMode::Quadrants => (2, 2),
+            PixelMode::Braille => (2, 4),
+            PixelMode::Sextants => (2, 3),
+            PixelMode::Octants => (2, 4),
+        }
+    }
+
+    /// Returns `true` if the characters of this mode are from the Symbols for Legacy Computing.
+    pub(crate) fn needs_legacy_symbols(self) -> bool {
+        matches!(self, PixelMode::Sextants | PixelMode::Octants)
+    }
+
+    fn pixel_count(self) -> usize {
+        let (columns, rows) = self.size();
+        columns * rows
+    }
+}
+
+/// The pixels of a cell drawn with a [`PixelMode`] other than [`PixelMode::HalfBlocks`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mosaic {
+    /// The mode that the pixels were drawn with.
+    pub mode: PixelMode,
+    /// The pixels from left to right and top to bottom.
+    ///
+    /// Only as many pixels as the mode has are used. The rest are [`Block::Empty`].
+    pub pixels: [Block; 8],
+}
+
+/// The quadrant characters indexed by a pattern of the quadrants from left to right and top to bottom,
+/// with the top-left quadrant as the lowest bit.
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// The bits of the braille dots in the braille block (U+2800 to U+28FF)
+/// from left to right and top to bottom.
+const BRAILLE_DOTS: [u32; 8] = [0x01, 0x08, 0x02, 0x10, 0x04, 0x20, 0x40, 0x80];
+
+impl Mosaic {
+    /// Returns a mosaic of the upper and lower half blocks of a cell.
+    pub(crate) fn from_halves(upper_block: Block, lower_block: Block) -> Mosaic {
+        let mut pixels = [Block::Empty; 8];
+        pixels[0] = upper_block;
+        pixels[1] = lower_block;
+        Mosaic {
+            mode: PixelMode::HalfBlocks,
+            pixels,
+        }
+    }
+
+    /// Returns the pixels that are used.
+    pub(crate) fn used_pixels(&self) -> &[Block] {
+        let (columns, rows) = self.mode.size();
+        &self.pixels[..columns * rows]
+    }
+
+    /// Returns `true` if all pixels are [`Block::Empty`].
+    pub(crate) fn is_empty(&self) -> bool {
+        self.used_pixels().iter().all(|pixel| pixel.is_empty())
+    }
+
+    /// Returns the pixels converted to `mode`.
+    ///
+    /// Every pixel becomes the most common of the pixels it overlaps that aren't empty.
+    pub(crate) fn resample(&self, mode: PixelMode) -> Mosaic {
+        let mut mosaic = Mosaic {
+            mode,
+            pixels: [Block::Empty; 8],
+        };
+        let (columns, rows) = mode.size();
+        for row in 0..rows {
+            for column in 0..columns {
+                mosaic.pixels[column + columns * row] =
+                    most_common(self.overlapped_pixels(mode, column, row)).unwrap_or(Block::Empty);
+            }
+        }
+        mosaic
+    }
+
+    /// Sets the pixel at `column` and `row` of `mode` to `block`.
+    ///
+    /// If this mosaic has at least as many pixels as `mode`, all of its pixels that the pixel overlaps are set.
+    /// Otherwise this mosaic is converted to `mode` first.
+    pub(crate) fn put(&mut self, mode: PixelMode, column: usize, row: usize, block: Block) {
+        if self.used_pixels().len() < mode.pixel_count() {
+            *self = self.resample(mode);
+        }
+
+        let (columns, rows) = self.mode.size();
+        for own_row in 0..rows {
+            for own_column in 0..columns {
+                if overlaps(self.mode, own_column, own_row, mode, column, row) {
+                    self.pixels[own_column + columns * own_row] = block;
+                }
+            }
+        }
+    }
+
+    /// Returns the pixel at `column` and `row` of `mode`.
+    pub(crate) fn get(&self, mode: PixelMode, column: usize, row: usize) -> Block {
+        if self.mode == mode {
+            self.pixels[column + mode.size().0 * row]
+        } else {
+            most_common(self.overlapped_pixels(mode, column, row)).unwrap_or(Block::Empty)
+        }
+    }
+
+    /// Returns the pixels that the pixel at `column` and `row` of `mode` overlaps and that aren't empty.
+    fn overlapped_pixels(
+        &self,
+        mode: PixelMode,
+        column: usize,
+        row: usize,
+    ) -> impl Iterator<Item = Block> + Clone + '_ {
+        let (columns, _) = self.mode.size();
+        self.used_pixels()
+            .iter()
+            .enumerate()
+            .filter(move |(index, _)| {
+                overlaps(
+                    self.mode,
+                    index % columns,
+                    index / columns,
+                    mode,
+                    column,
+                    row,
+                )
+            })
+            .map(|(_, pixel)| *pixel)
+            .filter(|pixel| !pixel.is_empty())
+    }
+
+    /// Returns the character that represents the pixels and the foreground and background colors it's drawn with.
+    ///
+    /// [`None`] stands for the terminal's default color.
+    pub(crate) fn appearance(&self) -> (char, Option<Color>, Option<Color>) {
+        let pixels = self.used_pixels();
+        let mut foreground = most_common(pixels.iter().copied().filter(|pixel| !pixel.is_empty()))
+            .unwrap_or(Block::Empty);
+        if self.mode == PixelMode::Braille {
+            return (self.plain_char(), block_color(foreground), None);
+        }
+
+        let mut background =
+            most_common(pixels.iter().copied().filter(|pixel| *pixel != foreground))
+                .unwrap_or(Block::Empty);
+
+        let mut pattern = 0;
+        for (index, pixel) in pixels.iter().enumerate() {
+            let is_foreground = *pixel == foreground
+                || *pixel != background
+                    && distance(*pixel, foreground) <= distance(*pixel, background);
+            if is_foreground {
+                pattern |= 1 << index;
+            }
+        }
+
+        if background == Block::Uncolored {
+            // The terminal's default foreground color can only be drawn as a foreground color
+            std::mem::swap(&mut foreground, &mut background);
+            pattern ^= (1 << pixels.len()) - 1;
+        }
+
+        (
+            self.glyph(pattern),
+            block_color(foreground),
+            block_color(background),
+        )
+    }
+
+    /// Returns the character that represents the pixels that aren't empty.
+    pub(crate) fn plain_char(&self) -> char {
+        let pattern = self
+            .used_pixels()
+            .iter()
+            .enumerate()
+            .filter(|(_, pixel)| !pixel.is_empty())
+            .fold(0, |pattern, (index, _)| pattern | 1 << index);
+        self.glyph(pattern)
+    }
+
+    /// Returns the character for a pattern of pixels from left to right and top to bottom,
+    /// with the top-left pixel as the lowest bit.
+    fn glyph(&self, pattern: usize) -> char {
+        match self.mode {
+            PixelMode::HalfBlocks => [' ', '▀', '▄', '█'][pattern],
+            PixelMode::Quadrants => QUADRANTS[pattern],
+            PixelMode::Braille => {
+                // Braille dots are numbered from top to bottom and the lowest row was added last
+                let dots = BRAILLE_DOTS
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| pattern & 1 << index != 0)
+                    .fold(0, |dots, (_, dot)| dots | dot);
+                char::from_u32(0x2800 + dots).unwrap()
+            }
+            PixelMode::Sextants => sextant(pattern),
+            PixelMode::Octants => octant(pattern),
+        }
+    }
+
+    /// Returns the upper and lower half blocks that approximate the pixels.
+    pub(crate) fn halves(&self) -> (Block, Block) {
+        let halves = self.resample(PixelMode::HalfBlocks);
+        (halves.pixels[0], halves.pixels[1])
+    }
+}
+
+/// Returns the sextant character for a pattern of sextants from left to right and top to bottom,
+/// with the top-left sextant as the lowest bit.
+fn sextant(pattern: usize) -> char {
+    match pattern {
+        0b000000 => ' ',
+        0b010101 => '▌',
+        0b101010 => '▐',
+        0b111111 => '█',
+        _ => {
+            // The sextants are in the order of their patterns,
+            // except for the ones that existed before and are skipped
+            let skipped = [0b010101, 0b101010]
+                .iter()
+                .filter(|skipped_pattern| pattern > **skipped_pattern)
+                .count();
+            char::from_u32(0x1FB00 + (pattern - 1 - skipped) as u32).unwrap()
+        }
+    }
+}
+
+/// The octant patterns that are drawn with characters that existed before the octants,
+/// other than the ones that are drawn with quadrants.
+const OCTANT_EXCEPTIONS: [(usize, char); 10] = [
+    (0b00000001, '\u{1CEA8}'),
+    (0b00000010, '\u{1CEAB}'),
+    (0b00000011, '\u{1FB82}'),
+    (0b00010100, '\u{1FBE6}'),
+    (0b00101000, '\u{1FBE7}'),
+    (0b00111111, '\u{1FB85}'),
+    (0b01000000, '\u{1CEA3}'),
+    (0b10000000, '\u{1CEA0}'),
+    (0b11000000, '▂'),
+    (0b11111100, '▆'),
+];
+
+/// Returns the octant character for a pattern of octants from left to right and top to bottom,
+/// with the top-left octant as the lowest bit.
+fn octant(pattern: usize) -> char {
+    if let Some(quadrant_pattern) = octant_quadrants(pattern) {
+        return QUADRANTS[quadrant_pattern];
+    }
+    if let Some((_, char)) = OCTANT_EXCEPTIONS
+        .iter()
+        .find(|(exception, _)| *exception == pattern)
+    {
+        return *char;
+    }
+
+    // The octants are in the order of their patterns,
+    // except for the ones that existed before and are skipped
+    let skipped = (0..pattern)
+        .filter(|skipped_pattern| {
+            octant_quadrants(*skipped_pattern).is_some()
+                || OCTANT_EXCEPTIONS
+                    .iter()
+                    .any(|(exception, _)| exception == skipped_pattern)
+        })
+        .count();
+    char::from_u32(0x1CD00 + (pattern - skipped) as u32).unwrap()
+}
+
+/// Returns the quadrant pattern that looks like the octant pattern, if there is one.
+fn octant_quadrants(pattern: usize) -> Option<usize> {
+    let octant = |index: usize| pattern >> index & 1;
+    let is_quadrant = octant(0) == octant(2)
+        && octant(1) == octant(3)
+        && octant(4) == octant(6)
+        && octant(5) == octant(7);
+    if is_quadrant {
+        Some(octant(0) | octant(1) << 1 | octant(4) << 2 | octant(5) << 3)
+    } else {
+        None
+    }
+}
+
+/// Returns `true` if the pixel at `column` and `row` of `mode` and the pixel at `other_column` and `other_row`
+/// of `other_mode` overlap.
+fn overlaps(
+    mode: PixelMode,
+    column: usize,
+    row: usize,
+    other_mode: PixelMode,
+    other_column: usize,
+    other_row: usize,
+) -> bool {
+    // Two ranges from `a / a_count` to `(a + 1) / a_count` and from `b / b_count` to `(b + 1) / b_count` overlap
+    // if each starts before the other ends
+    let overlap = |a: usize, a_count: usize, b: usize, b_count: usize| {
+        a * b_count < (b + 1) * a_count && b * a_count < (a + 1) * b_count
+    };
+    let (columns, rows) = mode.size();
+    let (other_columns, other_rows) = other_mode.size();
+    overlap(column, columns, other_column, other_columns)
+        && overlap(row, rows, other_row, other_rows)
+}
+
+/// Returns the most common of `blocks`, preferring the one that comes first if several are equally common.
+fn most_common(blocks: impl Iterator<Item = Block> + Clone) -> Option<Block> {
+    let mut most_common = None;
+    let mut most_common_count = 0;
+    for block in blocks.clone() {
+        let count = blocks.clone().filter(|other| *other == block).count();
+        if count > most_common_count {
+            most_common = Some(block);
+            most_common_count = count;
+        }
+    }
+    most_common
+}
+
+/// Returns the color `block` is drawn in or [`None`] for the terminal's default color.
+fn block_color(block: Block) -> Option<Color> {
+    match block {
+        Block::Colored(color) => Some(color),
+        _ => None,
+    }
+}
+
+/// Returns how different `a` and `b` look.
+///
+/// The terminal is assumed to draw white on black by default.
+fn distance(a: Block, b: Block) -> u32 {
+    let rgb = |block| match block {
+        Block::Empty => (0, 0, 0),
+        Block::Uncolored => (255, 255, 255),
+        Block::Colored(color) => color::to_rgb(color).unwrap_or((255, 255, 255)),
+    };
+    color::distance(rgb(a), rgb(b), Quantization::Perceptual)
+}
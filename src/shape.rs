@@ -0,0 +1,323 @@
+use crate::Color;
+use std::ops::RangeInclusive;
+
+/// The range of pixels that is drawn to, including all edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Bounds {
+    pub(crate) left: i64,
+    pub(crate) top: i64,
+    pub(crate) right: i64,
+    pub(crate) bottom: i64,
+}
+
+impl Bounds {
+    /// Returns the bounds grown by `margin` pixels on every side.
+    pub(crate) fn grown(self, margin: i64) -> Bounds {
+        Bounds {
+            left: self.left - margin,
+            top: self.top - margin,
+            right: self.right + margin,
+            bottom: self.bottom + margin,
+        }
+    }
+}
+
+/// Calls `plot` with every pixel within `bounds` of the line from (`x0`, `y0`) to (`x1`, `y1`)
+/// using Bresenham's line algorithm.
+pub(crate) fn line(
+    x0: i64,
+    y0: i64,
+    x1: i64,
+    y1: i64,
+    bounds: Bounds,
+    mut plot: impl FnMut(i64, i64),
+) {
+    let (delta_x, delta_y) = ((x1 - x0).abs(), (y1 - y0).abs());
+    let (step_x, step_y) = ((x1 - x0).signum(), (y1 - y0).signum());
+    if delta_x >= delta_y {
+        let steps = visible_steps(x0, step_x, delta_x, bounds.left, bounds.right);
+        for step in steps {
+            let y = y0 + step_y * minor_steps(step, delta_x, delta_y);
+            if y >= bounds.top && y <= bounds.bottom {
+                plot(x0 + step_x * step, y);
+            }
+        }
+    } else {
+        let steps = visible_steps(y0, step_y, delta_y, bounds.top, bounds.bottom);
+        for step in steps {
+            let x = x0 + step_x * minor_steps(step, delta_y, delta_x);
+            if x >= bounds.left && x <= bounds.right {
+                plot(x, y0 + step_y * step);
+            }
+        }
+    }
+}
+
+/// Returns the steps along the longer axis of a line, starting at `start` and taking `length` steps of `step`,
+/// whose position is between `first` and `last`.
+fn visible_steps(start: i64, step: i64, length: i64, first: i64, last: i64) -> RangeInclusive<i64> {
+    let (first_step, last_step) = if step < 0 {
+        (start - last, start - first)
+    } else {
+        (first - start, last - start)
+    };
+    first_step.max(0)..=last_step.min(length)
+}
+
+/// Returns how many steps along the shorter axis Bresenham's line algorithm has taken
+/// after `step` steps along the longer axis of a line that is `major_length` by `minor_length` long.
+///
+/// The lengths can be up to twice [`i32::MAX`] so the calculation is done in 128 bits.
+fn minor_steps(step: i64, major_length: i64, minor_length: i64) -> i64 {
+    // A step is taken whenever the line is at least half a pixel off, which is when it passes
+    // the middle between two pixels
+    if major_length == 0 {
+        return 0;
+    }
+    let (step, major_length, minor_length) =
+        (step as i128, major_length as i128, minor_length as i128);
+    ((2 * minor_length * step - major_length).div_euclid(2 * major_length) + 1) as i64
+}
+
+/// Calls `plot` with every pixel within `bounds` of a round dot that is `thickness` pixels wide
+/// around (`x`, `y`).
+///
+/// Dots with an even thickness extend one pixel further to the right and bottom.
+/// Dots that are 0 pixels wide have no pixels.
+/// The squared distances can exceed 64 bits for large thicknesses so they are compared in 128 bits.
+pub(crate) fn dot(x: i64, y: i64, thickness: u32, bounds: Bounds, mut plot: impl FnMut(i64, i64)) {
+    let thickness = thickness as i64;
+    let thickness_squared = thickness as i128 * thickness as i128;
+    // The distances are doubled so that even thicknesses, whose center is between pixels, stay in integers
+    let center_offset = 1 - thickness % 2;
+    let (first_offset, last_offset) = (-(thickness - 1) / 2, thickness / 2);
+    for offset_y in first_offset.max(bounds.top - y)..=last_offset.min(bounds.bottom - y) {
+        for offset_x in first_offset.max(bounds.left - x)..=last_offset.min(bounds.right - x) {
+            let distance_x = (2 * offset_x - center_offset) as i128;
+            let distance_y = (2 * offset_y - center_offset) as i128;
+            if distance_x * distance_x + distance_y * distance_y <= thickness_squared {
+                plot(x + offset_x, y + offset_y);
+            }
+        }
+    }
+}
+
+/// The colors of the edges of a rectangle. See [`Buffer::edge_colored_rectangle`](crate::Buffer::edge_colored_rectangle).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeColors {
+    pub top: Color,
+    pub right: Color,
+    pub bottom: Color,
+    pub left: Color,
+}
+
+impl EdgeColors {
+    /// Returns edge colors that are all `color`.
+    pub fn all(color: Color) -> EdgeColors {
+        EdgeColors {
+            top: color,
+            right: color,
+            bottom: color,
+            left: color,
+        }
+    }
+
+    /// Returns the color of the edge that the pixel at `column` and `row` of a rectangle
+    /// `width` pixels wide and `height` pixels high is closest to.
+    ///
+    /// The corners belong to the top and bottom edges.
+    pub(crate) fn at(&self, column: i64, row: i64, width: i64, height: i64) -> Color {
+        [
+            (row, self.top),
+            (height - 1 - row, self.bottom),
+            (column, self.left),
+            (width - 1 - column, self.right),
+        ]
+        .iter()
+        .min_by_key(|(distance, _)| *distance)
+        .unwrap()
+        .1
+    }
+}
+
+/// Returns how far `row` of a rectangle `height` pixels high with corners rounded by `radius` is indented
+/// on both sides.
+pub(crate) fn corner_inset(row: i64, height: i64, radius: i64) -> i64 {
+    let row_from_edge = row.min(height - 1 - row);
+    if row_from_edge >= radius {
+        return 0;
+    }
+
+    // The distance from the center of the pixel row to the center of the corner's circle
+    let distance = radius as f64 - (row_from_edge as f64 + 0.5);
+    (radius as f64 - (radius as f64 * radius as f64 - distance * distance).sqrt()).round() as i64
+}
+
+/// Returns how many pixels an ellipse with the radii `radius_x` and `radius_y` extends to the left and right
+/// of its center in the row `offset_y` pixels below or above the center, which is at most `radius_y`.
+///
+/// Like in the midpoint ellipse algorithm, a pixel is part of the ellipse if the point half a pixel closer
+/// to the center, either horizontally or vertically, is inside of it.
+/// The radii can be at most [`i32::MAX`] so that the calculations fit into 128 bits.
+pub(crate) fn ellipse_half_width(radius_x: i64, radius_y: i64, offset_y: i64) -> i64 {
+    if radius_y == 0 {
+        return radius_x;
+    }
+
+    let (radius_x, radius_y, offset_y) = (radius_x as i128, radius_y as i128, offset_y as i128);
+    let (radius_x_squared, radius_y_squared) = (radius_x * radius_x, radius_y * radius_y);
+    // The point half a pixel below is inside: 4ry²x² < rx²(4ry² - (2y - 1)²)
+    let vertical_limit =
+        radius_x_squared * (2 * radius_y - 2 * offset_y + 1) * (2 * radius_y + 2 * offset_y - 1);
+    let vertical_half_width = if vertical_limit > 0 {
+        square_root((vertical_limit - 1) / (4 * radius_y_squared))
+    } else {
+        0
+    };
+    // The point half a pixel to the left is inside: (2x - 1)² ≤ 4rx²(ry² - y²) / ry²
+    let horizontal_limit =
+        4 * radius_x_squared * (radius_y - offset_y) * (radius_y + offset_y) / radius_y_squared;
+    let horizontal_half_width = (square_root(horizontal_limit) + 1) / 2;
+    vertical_half_width.max(horizontal_half_width) as i64
+}
+
+/// Returns the square root of `number`, rounded down.
+fn square_root(number: i128) -> i128 {
+    let mut root = (number as f64).sqrt() as i128;
+    // The floating point square root can be off by a bit for large numbers
+    while root * root > number {
+        root -= 1;
+    }
+    while (root + 1) * (root + 1) <= number {
+        root += 1;
+    }
+    root
+}
+
+/// Returns `true` if `angle` is between `start_angle` and `end_angle` counterclockwise, all in degrees.
+pub(crate) fn is_between(angle: f32, start_angle: f32, end_angle: f32) -> bool {
+    if end_angle - start_angle >= 360.0 {
+        return true;
+    }
+    (angle - start_angle).rem_euclid(360.0) <= (end_angle - start_angle).rem_euclid(360.0)
+}
+
+/// Which parts of a polygon whose edges cross each other are filled.
+/// See [`Buffer::filled_polygon`](crate::Buffer::filled_polygon).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillRule {
+    /// A part is filled if a line from it to the outside crosses an odd number of edges.
+    ///
+    /// This leaves holes where the polygon overlaps itself, like in the middle of a pentagram.
+    EvenOdd,
+    /// A part is filled if the edges wind around it at least once in the same direction.
+    NonZero,
+}
+
+/// Calls `fill` with the first and the last column of every span of pixels in `row`
+/// that is inside of the polygon with the corners `points` according to `fill_rule`.
+///
+/// A pixel is inside of the polygon if its center is.
+pub(crate) fn polygon_spans(
+    points: &[(i64, i64)],
+    row: i64,
+    fill_rule: FillRule,
+    mut fill: impl FnMut(i64, i64),
+) {
+    let center_y = row as f64 + 0.5;
+    // Where the edges cross the row and whether they go down or up
+    let mut crossings = Vec::new();
+    for (index, start) in points.iter().enumerate() {
+        let end = points[(index + 1) % points.len()];
+        let ((start_x, start_y), (end_x, end_y)) = (
+            (start.0 as f64 + 0.5, start.1 as f64 + 0.5),
+            (end.0 as f64 + 0.5, end.1 as f64 + 0.5),
+        );
+        // The lower end of every edge is excluded so that corners aren't crossed twice
+        if (start_y <= center_y) != (end_y <= center_y) {
+            let x = start_x + (center_y - start_y) * (end_x - start_x) / (end_y - start_y);
+            let winding = if end_y > start_y { 1 } else { -1 };
+            crossings.push((x, winding));
+        }
+    }
+    crossings.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+
+    let mut winding = 0;
+    for (index, (x, crossing_winding)) in crossings.iter().enumerate() {
+        let is_inside = match fill_rule {
+            FillRule::EvenOdd => index % 2 == 0,
+            FillRule::NonZero => {
+                winding += crossing_winding;
+                winding != 0
+            }
+        };
+        if is_inside {
+            if let Some((next_x, _)) = crossings.get(index + 1) {
+                // The columns whose centers are between the crossings
+                let first_column = (x - 0.5).ceil() as i64;
+                let last_column = (next_x - 0.5).ceil() as i64 - 1;
+                if first_column <= last_column {
+                    fill(first_column, last_column);
+                }
+            }
+        }
+    }
+}
+
+/// Returns how much each of the `corners` of a triangle contributes to the point (`x`, `y`), adding up to 1.
+///
+/// Points outside of the triangle are treated like the closest point on its edges, roughly.
+/// The corners can be anywhere in the range of [`i32`] so the calculations are done in 128 bits.
+pub(crate) fn barycentric_weights(corners: [(i64, i64); 3], x: i64, y: i64) -> [f32; 3] {
+    let [(x0, y0), (x1, y1), (x2, y2)] = corners.map(|(x, y)| (x as i128, y as i128));
+    let (x, y) = (x as i128, y as i128);
+    let area = ((x1 - x0) * (y2 - y0) - (x2 - x0) * (y1 - y0)) as f32;
+    if area == 0.0 {
+        return [1.0, 0.0, 0.0];
+    }
+
+    let weight = |(ax, ay): (i128, i128), (bx, by): (i128, i128)| {
+        (((bx - ax) * (y - ay) - (x - ax) * (by - ay)) as f32 / area).max(0.0)
+    };
+    let weights = [
+        weight((x1, y1), (x2, y2)),
+        weight((x2, y2), (x0, y0)),
+        weight((x0, y0), (x1, y1)),
+    ];
+    let sum: f32 = weights.iter().sum();
+    if sum == 0.0 {
+        return [1.0, 0.0, 0.0];
+    }
+    [weights[0] / sum, weights[1] / sum, weights[2] / sum]
+}
+
+/// Which neighbors of a pixel belong to the same region.
+/// See [`Buffer::flood_fill`](crate::Buffer::flood_fill).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// The 4 pixels to the left, right, top and bottom.
+    Four,
+    /// The 4 pixels to the left, right, top and bottom and the 4 diagonal ones.
+    ///
+    /// Regions can leak through diagonal lines with this.
+    Eight,
+}
+
+impl Connectivity {
+    /// Returns the offsets from a pixel to its neighbors.
+    pub(crate) fn offsets(self) -> &'static [(i64, i64)] {
+        match self {
+            Connectivity::Four => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+            Connectivity::Eight => &[
+                (-1, 0),
+                (1, 0),
+                (0, -1),
+                (0, 1),
+                (-1, -1),
+                (1, -1),
+                (-1, 1),
+                (1, 1),
+            ],
+        }
+    }
+}